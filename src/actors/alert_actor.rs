@@ -0,0 +1,239 @@
+#[cfg(feature = "ssr")]
+pub mod alert_actors {
+    use crate::actors::HealthCheckInfo;
+    use crate::types::{AlertingSettings, CheckStatus, ServiceType, WebhookSettings};
+    use actix::prelude::*;
+    use chrono::{DateTime, Utc};
+    use log::{error, info, warn};
+    use serde::Serialize;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::broadcast;
+    use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+    use tokio_stream::wrappers::BroadcastStream;
+
+    #[derive(Serialize, Clone, Copy, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum AlertEvent {
+        Alert,
+        Recovery,
+    }
+
+    /// JSON body posted to webhooks when a service changes status.
+    #[derive(Serialize)]
+    pub struct AlertPayload {
+        pub event: AlertEvent,
+        pub service: String,
+        pub service_type: ServiceType,
+        pub url: String,
+        pub previous_status: CheckStatus,
+        pub status: CheckStatus,
+        pub status_message: String,
+        pub response_time: u128,
+        pub timestamp: DateTime<Utc>,
+    }
+
+    /// Notifies the configured webhooks whenever a service changes status.
+    pub struct AlertActor {
+        settings: Arc<AlertingSettings>,
+        client: reqwest::Client,
+        previous: HashMap<String, CheckStatus>,
+        receiver: Option<broadcast::Receiver<HealthCheckInfo>>,
+    }
+
+    impl AlertActor {
+        pub fn new(
+            settings: AlertingSettings,
+            receiver: broadcast::Receiver<HealthCheckInfo>,
+        ) -> reqwest::Result<Self> {
+            let client = reqwest::ClientBuilder::new()
+                .timeout(Duration::from_secs(settings.timeout_seconds))
+                .build()?;
+            Ok(AlertActor {
+                settings: Arc::new(settings),
+                client,
+                previous: HashMap::new(),
+                receiver: Some(receiver),
+            })
+        }
+
+        /// Returns the payload to send if `info` is a status transition worth reporting.
+        ///
+        /// Services are assumed healthy until their first result, so a service that is
+        /// already failing when the monitor starts still raises an alert.
+        fn transition(&mut self, info: &HealthCheckInfo) -> Option<AlertPayload> {
+            let status = info.latest_status.status;
+            let previous_status = self
+                .previous
+                .insert(info.name.clone(), status)
+                .unwrap_or(CheckStatus::Healthy);
+            if previous_status == status {
+                return None;
+            }
+            let event = if status == CheckStatus::Healthy {
+                AlertEvent::Recovery
+            } else {
+                AlertEvent::Alert
+            };
+            if event == AlertEvent::Recovery && !self.settings.notify_recovery {
+                return None;
+            }
+            Some(AlertPayload {
+                event,
+                service: info.name.clone(),
+                service_type: info.service_type,
+                url: info.url.clone(),
+                previous_status,
+                status,
+                status_message: info.latest_status.status_message.clone(),
+                response_time: info.latest_status.response_time,
+                timestamp: info.latest_status.timestamp,
+            })
+        }
+    }
+
+    impl Actor for AlertActor {
+        type Context = Context<Self>;
+
+        fn started(&mut self, ctx: &mut Self::Context) {
+            info!("Alert actor started");
+            if let Some(receiver) = self.receiver.take() {
+                ctx.add_stream(BroadcastStream::new(receiver));
+            }
+        }
+
+        fn stopped(&mut self, _ctx: &mut Self::Context) {
+            info!("Alert actor stopped");
+        }
+    }
+
+    impl StreamHandler<Result<HealthCheckInfo, BroadcastStreamRecvError>> for AlertActor {
+        fn handle(
+            &mut self,
+            msg: Result<HealthCheckInfo, BroadcastStreamRecvError>,
+            ctx: &mut Context<Self>,
+        ) {
+            let info = match msg {
+                Ok(info) => info,
+                Err(BroadcastStreamRecvError::Lagged(n)) => {
+                    warn!("Alert actor lagged, {} check results were not evaluated", n);
+                    return;
+                }
+            };
+            let Some(payload) = self.transition(&info) else {
+                return;
+            };
+            info!(
+                "Status of '{}' changed from {} to {}",
+                payload.service, payload.previous_status, payload.status
+            );
+
+            let settings = self.settings.clone();
+            let client = self.client.clone();
+            let fut = async move {
+                for webhook in settings.webhooks.iter().filter(|w| w.applies_to(&payload.service)) {
+                    deliver(&client, webhook, &payload, &settings).await;
+                }
+            };
+            ctx.spawn(fut.into_actor(self));
+        }
+    }
+
+    async fn deliver(
+        client: &reqwest::Client,
+        webhook: &WebhookSettings,
+        payload: &AlertPayload,
+        settings: &AlertingSettings,
+    ) {
+        for attempt in 0..=settings.retries {
+            if attempt > 0 {
+                tokio::time::sleep(Duration::from_millis(settings.retry_delay_ms)).await;
+            }
+            let result = client
+                .post(&webhook.url)
+                .json(payload)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            match result {
+                Ok(_) => {
+                    info!("Notified webhook '{}' about '{}'", webhook.name, payload.service);
+                    return;
+                }
+                Err(e) => warn!(
+                    "Webhook '{}' failed (attempt {}/{}): {}",
+                    webhook.name,
+                    attempt + 1,
+                    settings.retries + 1,
+                    e
+                ),
+            }
+        }
+        error!(
+            "Giving up on webhook '{}' for '{}' after {} attempts",
+            webhook.name,
+            payload.service,
+            settings.retries + 1
+        );
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::types::HealthCheckStatus;
+
+        fn info(name: &str, status: CheckStatus) -> HealthCheckInfo {
+            let check = HealthCheckStatus::new(status, "", 10);
+            HealthCheckInfo {
+                name: name.to_string(),
+                service_type: ServiceType::Http,
+                url: "http://localhost".to_string(),
+                interval_seconds: 30,
+                latest_status: check.clone(),
+                raw_status: check,
+                flapping: false,
+            }
+        }
+
+        fn actor(notify_recovery: bool) -> AlertActor {
+            let settings = AlertingSettings { notify_recovery, ..AlertingSettings::default() };
+            let (_, receiver) = broadcast::channel(1);
+            AlertActor::new(settings, receiver).unwrap()
+        }
+
+        #[test]
+        fn first_failure_is_a_transition_from_healthy() {
+            let mut actor = actor(true);
+            assert!(actor.transition(&info("up", CheckStatus::Healthy)).is_none());
+
+            let payload = actor.transition(&info("down", CheckStatus::Unhealthy)).unwrap();
+            assert!(payload.event == AlertEvent::Alert);
+            assert_eq!(payload.previous_status, CheckStatus::Healthy);
+            assert_eq!(payload.status, CheckStatus::Unhealthy);
+            assert!(actor.transition(&info("down", CheckStatus::Unhealthy)).is_none());
+        }
+
+        #[test]
+        fn reports_changes_and_recoveries() {
+            let mut actor = actor(true);
+            actor.transition(&info("api", CheckStatus::Healthy));
+
+            let payload = actor.transition(&info("api", CheckStatus::Degraded)).unwrap();
+            assert!(payload.event == AlertEvent::Alert);
+            let payload = actor.transition(&info("api", CheckStatus::Unhealthy)).unwrap();
+            assert_eq!(payload.previous_status, CheckStatus::Degraded);
+            let payload = actor.transition(&info("api", CheckStatus::Healthy)).unwrap();
+            assert!(payload.event == AlertEvent::Recovery);
+            assert_eq!(payload.previous_status, CheckStatus::Unhealthy);
+        }
+
+        #[test]
+        fn skips_recoveries_when_disabled() {
+            let mut actor = actor(false);
+            assert!(actor.transition(&info("api", CheckStatus::Unhealthy)).is_some());
+            assert!(actor.transition(&info("api", CheckStatus::Healthy)).is_none());
+            assert!(actor.transition(&info("api", CheckStatus::Unhealthy)).is_some());
+        }
+    }
+}
@@ -1,9 +1,10 @@
 #[cfg(feature = "ssr")]
 pub mod broadcast_actor {
-    use crate::types::{HealthCheckStatus, ServiceType};
+    use crate::types::{HealthCheckStatus, ServiceHealthCheckInfo, ServiceType};
     use actix::prelude::*;
     use log::info;
     use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
     use tokio::sync::broadcast;
 
     #[derive(Message, Serialize, Deserialize, Clone)]
@@ -14,15 +15,40 @@ pub mod broadcast_actor {
         pub url: String,
         pub interval_seconds: u64,
         pub latest_status: HealthCheckStatus,
+        pub raw_status: HealthCheckStatus,
+        pub flapping: bool,
     }
 
+    impl From<HealthCheckInfo> for ServiceHealthCheckInfo {
+        fn from(info: HealthCheckInfo) -> Self {
+            ServiceHealthCheckInfo {
+                name: info.name,
+                service_type: info.service_type,
+                url: info.url,
+                interval_seconds: info.interval_seconds,
+                latest_status: info.latest_status,
+                raw_status: info.raw_status,
+                flapping: info.flapping,
+            }
+        }
+    }
+
+    /// Requests the most recent result of every service, ordered by name.
+    #[derive(Message)]
+    #[rtype(result = "Vec<HealthCheckInfo>")]
+    pub struct GetLatest;
+
     pub struct BroadcastActor {
-        sender: broadcast::Sender<HealthCheckInfo>
+        sender: broadcast::Sender<HealthCheckInfo>,
+        latest: HashMap<String, HealthCheckInfo>,
     }
     
     impl BroadcastActor {
         pub fn new(sender: broadcast::Sender<HealthCheckInfo>) -> Self {
-            BroadcastActor { sender }
+            BroadcastActor {
+                sender,
+                latest: HashMap::new(),
+            }
         }
     }
 
@@ -42,7 +68,18 @@ pub mod broadcast_actor {
         type Result = ();
 
         fn handle(&mut self, msg: HealthCheckInfo, _ctx: &mut Context<Self>) -> Self::Result {
+            self.latest.insert(msg.name.clone(), msg.clone());
             self.sender.send(msg).ok();
         }
     }
+
+    impl Handler<GetLatest> for BroadcastActor {
+        type Result = MessageResult<GetLatest>;
+
+        fn handle(&mut self, _msg: GetLatest, _ctx: &mut Context<Self>) -> Self::Result {
+            let mut latest: Vec<HealthCheckInfo> = self.latest.values().cloned().collect();
+            latest.sort_by(|a, b| a.name.cmp(&b.name));
+            MessageResult(latest)
+        }
+    }
 }
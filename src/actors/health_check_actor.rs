@@ -3,12 +3,17 @@ pub mod health_check_actors {
     use crate::actors::broadcaster::broadcast_actor::{
         BroadcastActor, HealthCheckInfo,
     };
-    use crate::types::{CheckStatus, HealthCheckStatus, ServiceType};
+    use crate::checkers::HealthChecker;
+    use crate::types::{
+        CheckStatus, DampingSettings, GlobalSettings, HealthCheckStatus, LatencyThresholds,
+        RetrySettings, ServiceSettings, ServiceType,
+    };
     use actix::prelude::*;
     use anyhow::Result;
-    use chrono::Utc;
-    use log::{info, warn, error};
-    use reqwest::StatusCode;
+    use chrono::{DateTime, TimeDelta, Utc};
+    use std::collections::VecDeque;
+    use log::{info, warn};
+    use reqwest::Url;
     use std::sync::Arc;
     use std::time::{Duration, Instant};
     use tokio_stream::wrappers::IntervalStream;
@@ -18,46 +23,53 @@ pub mod health_check_actors {
     #[rtype(result = "Result<(), anyhow::Error>")]
     struct Check;
 
-    pub struct HttpHealthCheckActor {
+    /// Runs a `HealthChecker` on a fixed interval and broadcasts every result.
+    pub struct HealthCheckActor {
         name: String,
+        service_type: ServiceType,
         url: String,
         interval_seconds: u64,
         timeout: u64,
-        response_code: u16,
-        headers: Vec<String>,
+        thresholds: LatencyThresholds,
+        retry: RetrySettings,
+        damper: StatusDamper,
+        /// Set while a check and its retries run; ticks arriving meanwhile are skipped.
+        in_flight: bool,
+        checker: Arc<dyn HealthChecker>,
         broadcast_actor: Arc<Addr<BroadcastActor>>,
     }
 
-    impl HttpHealthCheckActor {
+    impl HealthCheckActor {
         pub fn new(
-            name: String,
-            url: String,
-            interval_seconds: u64,
-            timeout: u64,
-            response_code: u16,
-            headers: Vec<String>,
+            service: &ServiceSettings,
+            global: &GlobalSettings,
+            checker: Arc<dyn HealthChecker>,
             broadcast_actor: Arc<Addr<BroadcastActor>>,
         ) -> Self {
-            HttpHealthCheckActor {
-                name,
-                url,
-                interval_seconds,
-                timeout,
-                response_code,
-                headers,
+            HealthCheckActor {
+                name: service.name.clone(),
+                service_type: service.service_type,
+                url: redact_password(&service.url),
+                interval_seconds: service.interval_seconds(global),
+                timeout: service.timeout_seconds.unwrap_or(global.timeout_seconds),
+                thresholds: service.latency_thresholds(global),
+                retry: service.retry_settings(),
+                damper: StatusDamper::new(service.damping_settings()),
+                in_flight: false,
+                checker,
                 broadcast_actor,
             }
         }
     }
 
-    impl Actor for HttpHealthCheckActor {
+    impl Actor for HealthCheckActor {
         type Context = Context<Self>;
 
         fn started(&mut self, ctx: &mut Self::Context) {
             info!(
                 "Health check actor started, name: {}, type: {}",
                 self.name,
-                ServiceType::Http
+                self.service_type
             );
             let stream = IntervalStream::new(tokio::time::interval(Duration::from_secs(
                 self.interval_seconds,
@@ -70,87 +82,269 @@ pub mod health_check_actors {
             info!(
                 "Health check actor stoped, name: {}, type: {}",
                 self.name,
-                ServiceType::Http
+                self.service_type
             );
         }
     }
 
-    impl StreamHandler<Check> for HttpHealthCheckActor {
+    impl StreamHandler<Check> for HealthCheckActor {
         fn handle(&mut self, _msg: Check, ctx: &mut Context<Self>) {
-            let broadcast_actor = self.broadcast_actor.clone();
-            let url = self.url.clone();
+            if self.in_flight {
+                warn!("Previous check for '{}' is still running, skipping this one", self.name);
+                return;
+            }
+            self.in_flight = true;
+            let checker = self.checker.clone();
             let name = self.name.clone();
-            let timeout = self.timeout;
-            let interval = self.interval_seconds;
+            let timeout = Duration::from_secs(self.timeout);
+            let thresholds = self.thresholds;
+            let retry = self.retry;
+            // Retries end before the next tick is due, so results arrive in order.
+            let deadline = Instant::now() + Duration::from_secs(self.interval_seconds);
 
             let fut = async move {
-                let start = Instant::now();
-                let client = match reqwest::ClientBuilder::new()
-                    .timeout(Duration::from_secs(timeout))
-                    .build()
-                {
-                    Ok(c) => c,
-                    Err(e) => {
-                        error!("Failed to build reqwest client: {}", e);
-                        return;
+                let mut attempts = 1;
+                let mut delay = Duration::from_millis(retry.retry_delay_ms);
+                loop {
+                    let mut status = check_once(checker.as_ref(), &name, timeout).await;
+                    let out_of_time = Instant::now() + delay + timeout >= deadline;
+                    if status.status != CheckStatus::Unhealthy
+                        || attempts > retry.retries
+                        || out_of_time
+                    {
+                        status.attempts = attempts;
+                        return thresholds.apply(status);
                     }
-                };
-                
-                let health_status = match client.get(&url).send().await {
-                    Ok(response) => {
-                        let elapsed = start.elapsed().as_millis();
-                        info!("checked successful. name: {}, response time: {}ms", name, elapsed);
-                        match response.error_for_status() {
-                            Ok(successful_response) => {
-                                let status = successful_response.status();
-                                let status_message = status.canonical_reason().unwrap_or("OK").to_string();
-                                HealthCheckStatus {
-                                    status: CheckStatus::from(status),
-                                    status_message,
-                                    response_time: start.elapsed().as_millis(),
-                                    timestamp: Utc::now(),
-                                }
-                            }
-                            Err(e) => {
-                                let status = e.status().unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-                                warn!("HTTP status error for '{}': {}", name, e);
-                                HealthCheckStatus {
-                                    status: CheckStatus::from(status),
-                                    status_message: e.to_string(),
-                                    response_time: elapsed,
-                                    timestamp: Utc::now(),
-                                }
-                            }
-                        }
+                    info!(
+                        "Check failed for '{}' (attempt {}/{}), retrying in {}ms: {}",
+                        name,
+                        attempts,
+                        retry.retries + 1,
+                        delay.as_millis(),
+                        status.status_message
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempts += 1;
+                }
+            }
+            .into_actor(self)
+            .map(|raw_status, actor, _ctx| {
+                actor.in_flight = false;
+                let (latest_status, flapping) = actor.damper.update(&raw_status);
+                actor.broadcast_actor.do_send(HealthCheckInfo {
+                    name: actor.name.clone(),
+                    service_type: actor.service_type,
+                    url: actor.url.clone(),
+                    interval_seconds: actor.interval_seconds,
+                    latest_status,
+                    raw_status,
+                    flapping,
+                });
+            });
+            ctx.spawn(fut);
+        }
+    }
+
+    async fn check_once(checker: &dyn HealthChecker, name: &str, timeout: Duration) -> HealthCheckStatus {
+        let start = Instant::now();
+        match tokio::time::timeout(timeout, checker.check()).await {
+            Ok(status) => status,
+            Err(_) => {
+                let timeout = timeout.as_secs();
+                warn!("Health check timed out for '{}' after {}s", name, timeout);
+                HealthCheckStatus::unhealthy(
+                    format!("Timed out after {}s", timeout),
+                    start.elapsed().as_millis(),
+                )
+            }
+        }
+    }
+
+    /// Turns raw check results into the damped status reported to the dashboard and alerting.
+    ///
+    /// A service only becomes unhealthy after `failures_before_unhealthy` consecutive failed
+    /// checks and recovers after `successes_before_healthy` consecutive passing ones; moving
+    /// between healthy and degraded is immediate.
+    struct StatusDamper {
+        settings: DampingSettings,
+        effective: Option<CheckStatus>,
+        failures: u32,
+        successes: u32,
+        transitions: VecDeque<DateTime<Utc>>,
+    }
+
+    impl StatusDamper {
+        fn new(settings: DampingSettings) -> Self {
+            StatusDamper {
+                settings,
+                effective: None,
+                failures: 0,
+                successes: 0,
+                transitions: VecDeque::new(),
+            }
+        }
+
+        fn update(&mut self, raw: &HealthCheckStatus) -> (HealthCheckStatus, bool) {
+            let failed = raw.status == CheckStatus::Unhealthy;
+            if failed {
+                self.failures += 1;
+                self.successes = 0;
+            } else {
+                self.successes += 1;
+                self.failures = 0;
+            }
+
+            let next = match self.effective {
+                None => raw.status,
+                Some(CheckStatus::Unhealthy) if !failed => {
+                    if self.successes >= self.settings.successes_before_healthy {
+                        raw.status
+                    } else {
+                        CheckStatus::Unhealthy
                     }
-                    Err(e) => {
-                        error!("Request failed for '{}': {}", name, e);
-                        HealthCheckStatus {
-                            status: CheckStatus::Unhealthy,
-                            status_message: e.to_string(),
-                            response_time: start.elapsed().as_millis(),
-                            timestamp: Utc::now(),
-                        }
+                }
+                Some(current) if failed && current != CheckStatus::Unhealthy => {
+                    if self.failures >= self.settings.failures_before_unhealthy {
+                        CheckStatus::Unhealthy
+                    } else {
+                        current
                     }
-                };
-                broadcast_actor.do_send(HealthCheckInfo {
-                    name,
-                    service_type: ServiceType::Http,
-                    url,
-                    interval_seconds: interval,
-                    latest_status: health_status,
-                });
+                }
+                Some(_) => raw.status,
             };
-            ctx.spawn(fut.into_actor(self));
+            let changed = self.effective.is_some_and(|current| current != next);
+            if changed && self.settings.flap_threshold.is_some() {
+                self.transitions.push_back(raw.timestamp);
+            }
+            self.effective = Some(next);
+
+            let mut latest = raw.clone();
+            if next != raw.status {
+                latest.status = next;
+                latest.status_message =
+                    format!("Last check {}: {}", raw.status, raw.status_message);
+            }
+            (latest, self.is_flapping(raw.timestamp))
+        }
+
+        fn is_flapping(&mut self, now: DateTime<Utc>) -> bool {
+            let Some(threshold) = self.settings.flap_threshold else {
+                return false;
+            };
+            let window = TimeDelta::seconds(self.settings.flap_window_seconds as i64);
+            while self.transitions.front().is_some_and(|at| now - *at > window) {
+                self.transitions.pop_front();
+            }
+            self.transitions.len() >= threshold as usize
         }
     }
 
-    impl From<StatusCode> for CheckStatus {
-        fn from(item: StatusCode) -> Self {
-            match item {
-                item if item.is_success() => CheckStatus::Healthy,
-                _ => CheckStatus::Unhealthy,
+    /// Strips the password from a URL so credentials never reach the dashboard.
+    fn redact_password(url: &str) -> String {
+        match Url::parse(url) {
+            Ok(mut parsed) if parsed.password().is_some() => {
+                parsed.set_password(None).ok();
+                parsed.to_string()
             }
+            _ => url.to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use CheckStatus::{Degraded, Healthy, Unhealthy};
+
+        fn damper(failures: u32, successes: u32, flap_threshold: Option<u32>) -> StatusDamper {
+            StatusDamper::new(DampingSettings {
+                failures_before_unhealthy: failures,
+                successes_before_healthy: successes,
+                flap_threshold,
+                flap_window_seconds: 60,
+            })
+        }
+
+        /// Feeds raw results taken `seconds` apart and returns the damped statuses.
+        fn run(damper: &mut StatusDamper, raw: &[(CheckStatus, i64)]) -> Vec<(CheckStatus, bool)> {
+            let start = Utc::now();
+            raw.iter()
+                .map(|(status, seconds)| {
+                    let mut check = HealthCheckStatus::new(*status, "raw", 1);
+                    check.timestamp = start + TimeDelta::seconds(*seconds);
+                    let (latest, flapping) = damper.update(&check);
+                    (latest.status, flapping)
+                })
+                .collect()
+        }
+
+        fn statuses(damper: &mut StatusDamper, raw: &[CheckStatus]) -> Vec<CheckStatus> {
+            let timed: Vec<_> = raw.iter().map(|status| (*status, 0)).collect();
+            run(damper, &timed).into_iter().map(|(status, _)| status).collect()
+        }
+
+        #[test]
+        fn first_result_is_reported_as_is() {
+            assert_eq!(statuses(&mut damper(3, 3, None), &[Unhealthy]), [Unhealthy]);
+        }
+
+        #[test]
+        fn turns_unhealthy_after_consecutive_failures() {
+            let mut damper = damper(3, 1, None);
+            assert_eq!(
+                statuses(&mut damper, &[Healthy, Unhealthy, Unhealthy, Healthy, Unhealthy, Unhealthy, Unhealthy]),
+                [Healthy, Healthy, Healthy, Healthy, Healthy, Healthy, Unhealthy]
+            );
+        }
+
+        #[test]
+        fn explains_a_held_back_status() {
+            let mut damper = damper(2, 1, None);
+            run(&mut damper, &[(Healthy, 0)]);
+            let mut failed = HealthCheckStatus::unhealthy("connection refused", 1);
+            failed.timestamp = Utc::now();
+            let (latest, _) = damper.update(&failed);
+            assert_eq!(latest.status, Healthy);
+            assert_eq!(latest.status_message, "Last check Unhealthy: connection refused");
+        }
+
+        #[test]
+        fn recovers_after_consecutive_successes() {
+            let mut damper = damper(1, 2, None);
+            assert_eq!(
+                statuses(&mut damper, &[Unhealthy, Healthy, Unhealthy, Degraded, Healthy]),
+                [Unhealthy, Unhealthy, Unhealthy, Unhealthy, Healthy]
+            );
+        }
+
+        #[test]
+        fn moves_between_healthy_and_degraded_immediately() {
+            let mut damper = damper(3, 3, None);
+            assert_eq!(
+                statuses(&mut damper, &[Healthy, Degraded, Healthy, Degraded, Unhealthy]),
+                [Healthy, Degraded, Healthy, Degraded, Degraded]
+            );
+        }
+
+        #[test]
+        fn flags_flapping_within_the_window() {
+            let mut damper = damper(1, 1, Some(3));
+            let flapping: Vec<bool> = run(
+                &mut damper,
+                &[(Healthy, 0), (Unhealthy, 10), (Healthy, 20), (Unhealthy, 30), (Unhealthy, 85), (Unhealthy, 200)],
+            )
+            .into_iter()
+            .map(|(_, flapping)| flapping)
+            .collect();
+            // Three transitions by t=30; at t=85 the first one is older than the 60s window.
+            assert_eq!(flapping, [false, false, false, true, false, false]);
+        }
+
+        #[test]
+        fn never_flaps_without_a_threshold() {
+            let mut damper = damper(1, 1, None);
+            let results = run(&mut damper, &[(Healthy, 0), (Unhealthy, 1), (Healthy, 2), (Unhealthy, 3)]);
+            assert!(results.iter().all(|(_, flapping)| !flapping));
         }
     }
 }
@@ -0,0 +1,55 @@
+#[cfg(feature = "ssr")]
+pub mod metrics_actors {
+    use crate::actors::HealthCheckInfo;
+    use crate::metrics::HealthMetrics;
+    use actix::prelude::*;
+    use log::{info, warn};
+    use tokio::sync::broadcast;
+    use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+    use tokio_stream::wrappers::BroadcastStream;
+
+    /// Feeds every result published by the `BroadcastActor` into the Prometheus metrics.
+    pub struct MetricsActor {
+        metrics: HealthMetrics,
+        receiver: Option<broadcast::Receiver<HealthCheckInfo>>,
+    }
+
+    impl MetricsActor {
+        pub fn new(metrics: HealthMetrics, receiver: broadcast::Receiver<HealthCheckInfo>) -> Self {
+            MetricsActor {
+                metrics,
+                receiver: Some(receiver),
+            }
+        }
+    }
+
+    impl Actor for MetricsActor {
+        type Context = Context<Self>;
+
+        fn started(&mut self, ctx: &mut Self::Context) {
+            info!("Metrics actor started");
+            if let Some(receiver) = self.receiver.take() {
+                ctx.add_stream(BroadcastStream::new(receiver));
+            }
+        }
+
+        fn stopped(&mut self, _ctx: &mut Self::Context) {
+            info!("Metrics actor stopped");
+        }
+    }
+
+    impl StreamHandler<Result<HealthCheckInfo, BroadcastStreamRecvError>> for MetricsActor {
+        fn handle(
+            &mut self,
+            msg: Result<HealthCheckInfo, BroadcastStreamRecvError>,
+            _ctx: &mut Context<Self>,
+        ) {
+            match msg {
+                Ok(info) => self.metrics.observe(&info),
+                Err(BroadcastStreamRecvError::Lagged(n)) => {
+                    warn!("Metrics actor lagged, {} check results were not recorded", n);
+                }
+            }
+        }
+    }
+}
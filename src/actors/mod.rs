@@ -1,9 +1,24 @@
+#[cfg(feature = "ssr")]
+mod alert_actor;
 mod broadcaster;
 #[cfg(feature = "ssr")]
 mod health_check_actor;
+#[cfg(feature = "ssr")]
+mod metrics_actor;
+#[cfg(feature = "ssr")]
+mod storage_actor;
 
 #[cfg(feature = "ssr")]
 pub use health_check_actor::health_check_actors::*;
 
 #[cfg(feature = "ssr")]
 pub use broadcaster::broadcast_actor::*;
+
+#[cfg(feature = "ssr")]
+pub use storage_actor::storage_actors::*;
+
+#[cfg(feature = "ssr")]
+pub use metrics_actor::metrics_actors::*;
+
+#[cfg(feature = "ssr")]
+pub use alert_actor::alert_actors::*;
@@ -0,0 +1,63 @@
+#[cfg(feature = "ssr")]
+pub mod storage_actors {
+    use crate::actors::HealthCheckInfo;
+    use crate::storage::HistoryStore;
+    use actix::prelude::*;
+    use log::{error, info, warn};
+    use tokio::sync::broadcast;
+    use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+    use tokio_stream::wrappers::BroadcastStream;
+
+    /// Persists every result published by the `BroadcastActor`.
+    pub struct StorageActor {
+        store: HistoryStore,
+        receiver: Option<broadcast::Receiver<HealthCheckInfo>>,
+    }
+
+    impl StorageActor {
+        pub fn new(store: HistoryStore, receiver: broadcast::Receiver<HealthCheckInfo>) -> Self {
+            StorageActor {
+                store,
+                receiver: Some(receiver),
+            }
+        }
+    }
+
+    impl Actor for StorageActor {
+        type Context = Context<Self>;
+
+        fn started(&mut self, ctx: &mut Self::Context) {
+            info!("Storage actor started");
+            if let Some(receiver) = self.receiver.take() {
+                ctx.add_stream(BroadcastStream::new(receiver));
+            }
+        }
+
+        fn stopped(&mut self, _ctx: &mut Self::Context) {
+            info!("Storage actor stopped");
+        }
+    }
+
+    impl StreamHandler<Result<HealthCheckInfo, BroadcastStreamRecvError>> for StorageActor {
+        fn handle(
+            &mut self,
+            msg: Result<HealthCheckInfo, BroadcastStreamRecvError>,
+            ctx: &mut Context<Self>,
+        ) {
+            match msg {
+                Ok(info) => {
+                    let store = self.store.clone();
+                    let fut = async move {
+                        if let Err(e) = store.insert(&info).await {
+                            error!("Failed to store check result for '{}': {}", info.name, e);
+                        }
+                    };
+                    ctx.spawn(fut.into_actor(self));
+                }
+                Err(BroadcastStreamRecvError::Lagged(n)) => {
+                    warn!("Storage actor lagged, {} check results were not stored", n);
+                }
+            }
+        }
+    }
+}
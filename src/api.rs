@@ -1,21 +1,116 @@
 pub mod server_api {
     use std::convert::Infallible;
     use std::time::Duration;
-    use actix_web::{get, web, Responder};
+    use actix::Addr;
+    use actix_web::{error, get, web, HttpResponse, Responder};
+    use chrono::{DateTime, TimeDelta, Utc};
+    use serde::Deserialize;
     use actix_web_lab::sse;
     use actix_web_lab::sse::Sse;
     use log::warn;
     use tokio::sync::broadcast;
     use tokio_stream::wrappers::BroadcastStream;
-    use futures_util::StreamExt as _;
-    use crate::actors::HealthCheckInfo;
+    use futures_util::{stream, StreamExt as _};
+    use crate::actors::{BroadcastActor, GetLatest, HealthCheckInfo};
+    use crate::metrics::HealthMetrics;
+    use crate::storage::HistoryStore;
+
+    #[derive(Deserialize)]
+    struct HistoryQuery {
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: Option<u32>,
+    }
+
+    #[derive(Deserialize, Default, Clone, Copy)]
+    enum UptimeWindow {
+        #[default]
+        #[serde(rename = "24h")]
+        Day,
+        #[serde(rename = "7d")]
+        Week,
+        #[serde(rename = "30d")]
+        Month,
+    }
+
+    impl UptimeWindow {
+        fn duration(self) -> TimeDelta {
+            match self {
+                UptimeWindow::Day => TimeDelta::hours(24),
+                UptimeWindow::Week => TimeDelta::days(7),
+                UptimeWindow::Month => TimeDelta::days(30),
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct UptimeQuery {
+        #[serde(default)]
+        window: UptimeWindow,
+    }
+
+    #[get("/api/services/{name}/history")]
+    async fn history_handler(
+        store: web::Data<HistoryStore>,
+        name: web::Path<String>,
+        query: web::Query<HistoryQuery>,
+    ) -> actix_web::Result<HttpResponse> {
+        let history = store
+            .history(&name, query.from, query.to, query.limit)
+            .await
+            .map_err(error::ErrorInternalServerError)?;
+        Ok(HttpResponse::Ok().json(history))
+    }
+
+    #[get("/api/services/{name}/uptime")]
+    async fn uptime_handler(
+        store: web::Data<HistoryStore>,
+        name: web::Path<String>,
+        query: web::Query<UptimeQuery>,
+    ) -> actix_web::Result<HttpResponse> {
+        let to = Utc::now();
+        let from = to - query.window.duration();
+        let report = store
+            .uptime(&name, from, to)
+            .await
+            .map_err(error::ErrorInternalServerError)?;
+        Ok(HttpResponse::Ok().json(report))
+    }
+
+    #[get("/api/services")]
+    async fn services_handler(
+        broadcaster: web::Data<Addr<BroadcastActor>>,
+    ) -> actix_web::Result<HttpResponse> {
+        let latest = broadcaster
+            .send(GetLatest)
+            .await
+            .map_err(error::ErrorInternalServerError)?;
+        Ok(HttpResponse::Ok().json(latest))
+    }
+
+    #[get("/metrics")]
+    async fn metrics_handler(
+        metrics: web::Data<HealthMetrics>,
+    ) -> actix_web::Result<HttpResponse> {
+        let body = metrics.render().map_err(error::ErrorInternalServerError)?;
+        Ok(HttpResponse::Ok()
+            .content_type(prometheus::TEXT_FORMAT)
+            .body(body))
+    }
 
     #[get("/api/events")]
     async fn sse_handler(
         sender: web::Data<broadcast::Sender<HealthCheckInfo>>,
+        broadcaster: web::Data<Addr<BroadcastActor>>,
     ) -> impl Responder {
+        // Subscribe before taking the snapshot so no result falls between the two.
         let receiver = sender.subscribe();
-        let stream = BroadcastStream::new(receiver)
+        let latest = broadcaster.send(GetLatest).await.unwrap_or_else(|e| {
+            warn!("Failed to fetch latest results for SSE client: {}", e);
+            Vec::new()
+        });
+        let stream = stream::iter(latest.into_iter().map(Ok))
+            .chain(BroadcastStream::new(receiver))
             .map(|event_result| {
                 let event = match event_result {
                     Ok(server_event) => {
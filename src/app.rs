@@ -1,11 +1,23 @@
-use chrono::Utc;
+use std::collections::BTreeMap;
 use leptos::prelude::*;
 use leptos_meta::{provide_meta_context, Stylesheet, Title};
 use leptos_router::{
     components::{Route, Router, Routes},
     StaticSegment, WildcardSegment,
 };
-use crate::types::{CheckStatus, HealthCheckStatus, ServiceHealthCheckInfo, ServiceType};
+use crate::types::{CheckStatus, ServiceHealthCheckInfo};
+
+/// Latest result per monitored service, keyed by service name.
+type ServiceMap = BTreeMap<String, RwSignal<ServiceHealthCheckInfo>>;
+
+/// Only the hydrated client ever leaves the `Connecting` state.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(not(feature = "hydrate"), allow(dead_code))]
+enum ConnectionState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
 
 #[component]
 pub fn App() -> impl IntoView {
@@ -60,65 +72,252 @@ pub fn App() -> impl IntoView {
     }
 }
 
+/// Latest result of every monitored service, used to render the first paint.
+#[server(endpoint = "latest_results")]
+pub async fn get_latest_results() -> Result<Vec<ServiceHealthCheckInfo>, ServerFnError> {
+    use crate::actors::{BroadcastActor, GetLatest};
+    use actix::Addr;
+    use actix_web::web::Data;
+
+    let broadcaster: Data<Addr<BroadcastActor>> = leptos_actix::extract().await?;
+    let latest = broadcaster.send(GetLatest).await.map_err(ServerFnError::new)?;
+    Ok(latest.into_iter().map(ServiceHealthCheckInfo::from).collect())
+}
+
 /// Renders the home page of your application.
 #[component]
 fn HomePage() -> impl IntoView {
-    let (service, _) = signal(ServiceHealthCheckInfo{
-        name: "Google".to_string(),
-        url: "https://google.com".to_string(),
-        service_type: ServiceType::Http,
-        interval_seconds: 30,
-        latest_status: HealthCheckStatus{
-            status: CheckStatus::Unhealthy,
-            status_message: "Ok".to_string(),
-            response_time: 21,
-            timestamp: Utc::now(),
-        },
-    });
+    let services = RwSignal::new(ServiceMap::new());
+    let connection = RwSignal::new(ConnectionState::Connecting);
+    let latest = Resource::new(|| (), |_| get_latest_results());
+
+    #[cfg(feature = "hydrate")]
+    {
+        let source = StoredValue::new_local(None::<web_sys::EventSource>);
+        Effect::new(move |_| live_events::connect(services, connection, source));
+        on_cleanup(move || {
+            if let Some(Some(source)) = source.try_get_value() {
+                source.close();
+            }
+        });
+    }
+
     view! {
-        <div class="view-content grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4 sm:gap-6">
-            <ServiceCard service_info=service />
-        </div>
+        <ConnectionIndicator state=connection />
+        <Suspense fallback=|| view! { <p class="text-slate-400">"Loading services..."</p> }>
+            {move || Suspend::new(async move {
+                match latest.await {
+                    Ok(results) => {
+                        for info in results {
+                            upsert_service(services, info);
+                        }
+                    }
+                    Err(e) => leptos::logging::warn!("Failed to load latest results: {}", e),
+                }
+                view! {
+                    <Show
+                        when=move || !services.with(|services| services.is_empty())
+                        fallback=|| {
+                            view! {
+                                <p class="text-slate-400">"Waiting for health check results..."</p>
+                            }
+                        }
+                    >
+                        <div class="view-content grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4 sm:gap-6">
+                            <For
+                                each=move || services.get()
+                                key=|(name, _)| name.clone()
+                                children=|(_, service)| {
+                                    view! { <ServiceCard service_info=service.into() /> }
+                                }
+                            />
+                        </div>
+                    </Show>
+                }
+            })}
+        </Suspense>
     }
 }
 
-#[component]
-fn ServiceCard(service_info: ReadSignal<ServiceHealthCheckInfo>) -> impl IntoView {
-    let info = service_info.get();
-    view! {
-        <div class=match info.latest_status.status {
-            CheckStatus::Healthy => {
-                "service-card rounded-lg shadow-lg overflow-hidden bg-green-500/10 border-green-500 border flex flex-col"
+/// Stores an incoming result, updating the existing card in place when the service is known.
+/// Results older than the one already shown are ignored, so a late snapshot never rewinds a card.
+fn upsert_service(services: RwSignal<ServiceMap>, info: ServiceHealthCheckInfo) {
+    let existing = services.with_untracked(|services| services.get(&info.name).copied());
+    match existing {
+        Some(service) => {
+            let is_newer = service.with_untracked(|current| {
+                current.latest_status.timestamp <= info.latest_status.timestamp
+            });
+            if is_newer {
+                service.set(info);
+            }
+        }
+        None => services.update(|services| {
+            services.insert(info.name.clone(), RwSignal::new(info));
+        }),
+    }
+}
+
+#[cfg(feature = "hydrate")]
+mod live_events {
+    use super::{upsert_service, ConnectionState, ServiceMap};
+    use crate::types::ServiceHealthCheckInfo;
+    use leptos::prelude::*;
+    use std::time::Duration;
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen::JsCast;
+    use web_sys::{EventSource, MessageEvent};
+
+    const EVENTS_URL: &str = "/api/events";
+    const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+    /// Opens the SSE stream and keeps `services` in sync with it.
+    ///
+    /// The browser retries dropped connections on its own; once it gives up and
+    /// closes the stream, a fresh `EventSource` is opened after `RECONNECT_DELAY`.
+    pub fn connect(
+        services: RwSignal<ServiceMap>,
+        connection: RwSignal<ConnectionState>,
+        source: StoredValue<Option<EventSource>, LocalStorage>,
+    ) {
+        if source.is_disposed() {
+            return;
+        }
+        let event_source = match EventSource::new(EVENTS_URL) {
+            Ok(event_source) => event_source,
+            Err(_) => {
+                connection.set(ConnectionState::Disconnected);
+                schedule_reconnect(services, connection, source);
+                return;
             }
-            CheckStatus::Degraded => {
-                "service-card rounded-lg shadow-lg overflow-hidden bg-orange-500/10 border-orange-500 border flex flex-col"
+        };
+
+        let on_open = Closure::<dyn FnMut()>::new(move || {
+            connection.set(ConnectionState::Connected);
+        });
+        event_source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        on_open.forget();
+
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(data) = event.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str::<ServiceHealthCheckInfo>(&data) {
+                Ok(info) => upsert_service(services, info),
+                Err(e) => leptos::logging::warn!("Ignoring malformed health check event: {}", e),
             }
-            CheckStatus::Unhealthy => {
-                "service-card rounded-lg shadow-lg overflow-hidden bg-red-500/10 border-red-500 border flex flex-col"
+        });
+        event_source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        on_message.forget();
+
+        let on_error = Closure::<dyn FnMut()>::new({
+            let event_source = event_source.clone();
+            move || match event_source.ready_state() {
+                // A server-sent `error` event, e.g. a lagged client; the stream itself is fine.
+                EventSource::OPEN => {}
+                EventSource::CONNECTING => connection.set(ConnectionState::Disconnected),
+                _ => {
+                    connection.set(ConnectionState::Disconnected);
+                    schedule_reconnect(services, connection, source);
+                }
             }
-        }>
-            <div class="p-3 sm:p-4 flex-grow">
-                <div class="flex items-center min-w-0">
-                    <StatusIcon status=info.clone().latest_status.status />
-                    <h3
-                        class=match info.latest_status.status {
-                            CheckStatus::Healthy => {
-                                "ml-2 text-base sm:text-lg font-semibold truncate text-green-400"
-                            }
-                            CheckStatus::Degraded => {
-                                "ml-2 text-base sm:text-lg font-semibold truncate text-orange-400"
-                            }
-                            CheckStatus::Unhealthy => {
-                                "ml-2 text-base sm:text-lg font-semibold truncate text-red-400"
+        });
+        event_source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        on_error.forget();
+
+        source.set_value(Some(event_source));
+    }
+
+    fn schedule_reconnect(
+        services: RwSignal<ServiceMap>,
+        connection: RwSignal<ConnectionState>,
+        source: StoredValue<Option<EventSource>, LocalStorage>,
+    ) {
+        set_timeout(move || connect(services, connection, source), RECONNECT_DELAY);
+    }
+}
+
+#[component]
+fn ConnectionIndicator(state: RwSignal<ConnectionState>) -> impl IntoView {
+    move || match state.get() {
+        ConnectionState::Connected => None,
+        ConnectionState::Connecting => Some(view! {
+            <div class="mb-4 rounded-md border border-slate-600 bg-slate-700/40 px-4 py-2 text-sm text-slate-300">
+                "Connecting to live updates..."
+            </div>
+        }),
+        ConnectionState::Disconnected => Some(view! {
+            <div class="mb-4 rounded-md border border-orange-500 bg-orange-500/10 px-4 py-2 text-sm text-orange-400">
+                "Live updates disconnected. Reconnecting..."
+            </div>
+        }),
+    }
+}
+
+#[component]
+fn ServiceCard(service_info: Signal<ServiceHealthCheckInfo>) -> impl IntoView {
+    move || {
+        let info = service_info.get();
+        view! {
+            <div class=match info.latest_status.status {
+                CheckStatus::Healthy => {
+                    "service-card rounded-lg shadow-lg overflow-hidden bg-green-500/10 border-green-500 border flex flex-col"
+                }
+                CheckStatus::Degraded => {
+                    "service-card rounded-lg shadow-lg overflow-hidden bg-orange-500/10 border-orange-500 border flex flex-col"
+                }
+                CheckStatus::Unhealthy => {
+                    "service-card rounded-lg shadow-lg overflow-hidden bg-red-500/10 border-red-500 border flex flex-col"
+                }
+            }>
+                <div class="p-3 sm:p-4 flex-grow">
+                    <div class="flex items-center min-w-0">
+                        <StatusIcon status=info.clone().latest_status.status />
+                        <h3
+                            class=match info.latest_status.status {
+                                CheckStatus::Healthy => {
+                                    "ml-2 text-base sm:text-lg font-semibold truncate text-green-400"
+                                }
+                                CheckStatus::Degraded => {
+                                    "ml-2 text-base sm:text-lg font-semibold truncate text-orange-400"
+                                }
+                                CheckStatus::Unhealthy => {
+                                    "ml-2 text-base sm:text-lg font-semibold truncate text-red-400"
+                                }
                             }
-                        }
-                        title=info.name
+                            title=info.name
+                        >
+                            {info.clone().name}
+                        </h3>
+                        <Show when=move || info.flapping>
+                            <span
+                                class="ml-auto rounded px-2 py-0.5 text-xs font-medium bg-purple-500/20 text-purple-300"
+                                title="Status changed repeatedly within the flap window"
+                            >
+                                "Flapping"
+                            </span>
+                        </Show>
+                    </div>
+                    <div class="mt-2 flex items-center justify-between text-sm text-slate-300">
+                        <span class="truncate" title=info.url.clone()>{info.url.clone()}</span>
+                        <span
+                            class="ml-2 font-mono"
+                            title=info.latest_status.timings.map(|timings| timings.to_string())
+                        >
+                            {format!("{}ms", info.latest_status.response_time)}
+                            {(info.latest_status.attempts > 1)
+                                .then(|| format!(" ({} attempts)", info.latest_status.attempts))}
+                        </span>
+                    </div>
+                    <p
+                        class="mt-1 text-xs text-slate-400 truncate"
+                        title=info.latest_status.status_message.clone()
                     >
-                        {info.clone().name}
-                    </h3>
+                        {info.latest_status.status_message.clone()}
+                    </p>
                 </div>
             </div>
-        </div>
+        }
     }
 }
 
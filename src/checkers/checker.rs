@@ -0,0 +1,66 @@
+#[cfg(feature = "ssr")]
+pub mod health_checker {
+    use crate::checkers::{DbChecker, DnsChecker, GrpcChecker, HttpChecker, RedisChecker, TcpChecker, TlsChecker};
+    use crate::types::{GlobalSettings, HealthCheckStatus, ServiceSettings, ServiceType};
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// A single probe of a monitored service.
+    ///
+    /// Scheduling, timeouts and broadcasting are handled by `HealthCheckActor`,
+    /// so implementations only need to perform one check and describe the outcome.
+    #[async_trait]
+    pub trait HealthChecker: Send + Sync {
+        async fn check(&self) -> HealthCheckStatus;
+    }
+
+    /// Builds a checker for a configured service.
+    pub type CheckerFactory =
+        fn(&ServiceSettings, &GlobalSettings) -> Result<Arc<dyn HealthChecker>>;
+
+    pub struct CheckerRegistry {
+        factories: HashMap<ServiceType, CheckerFactory>,
+    }
+
+    impl CheckerRegistry {
+        pub fn new() -> Self {
+            CheckerRegistry {
+                factories: HashMap::new(),
+            }
+        }
+
+        pub fn register(&mut self, service_type: ServiceType, factory: CheckerFactory) {
+            self.factories.insert(service_type, factory);
+        }
+
+        pub fn build(
+            &self,
+            service: &ServiceSettings,
+            global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            let factory = self.factories.get(&service.service_type).ok_or_else(|| {
+                anyhow!(
+                    "no health checker registered for service type '{}'",
+                    service.service_type
+                )
+            })?;
+            factory(service, global)
+        }
+    }
+
+    impl Default for CheckerRegistry {
+        fn default() -> Self {
+            let mut registry = CheckerRegistry::new();
+            registry.register(ServiceType::Http, HttpChecker::from_settings);
+            registry.register(ServiceType::Db, DbChecker::from_settings);
+            registry.register(ServiceType::Tcp, TcpChecker::from_settings);
+            registry.register(ServiceType::Tls, TlsChecker::from_settings);
+            registry.register(ServiceType::Dns, DnsChecker::from_settings);
+            registry.register(ServiceType::Redis, RedisChecker::from_settings);
+            registry.register(ServiceType::Grpc, GrpcChecker::from_settings);
+            registry
+        }
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(feature = "ssr")]
+pub mod db {
+    use crate::checkers::HealthChecker;
+    use crate::types::{ExpectedResult, GlobalSettings, HealthCheckStatus, ServiceSettings};
+    use anyhow::{bail, Result};
+    use async_trait::async_trait;
+    use log::{error, info};
+    use sqlx::any::{install_default_drivers, AnyRow};
+    use sqlx::{AnyConnection, Connection, Executor, Row};
+    use std::fmt::Display;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    pub const DEFAULT_PROBE_QUERY: &str = "SELECT 1";
+
+    /// First column of the first row returned by the probe query.
+    #[derive(Debug)]
+    enum Scalar {
+        Null,
+        Integer(i64),
+        Float(f64),
+        Text(String),
+        Bool(bool),
+    }
+
+    impl Scalar {
+        fn from_row(row: &AnyRow) -> Result<Self> {
+            if let Ok(value) = row.try_get::<Option<i64>, _>(0) {
+                return Ok(value.map_or(Scalar::Null, Scalar::Integer));
+            }
+            if let Ok(value) = row.try_get::<Option<f64>, _>(0) {
+                return Ok(value.map_or(Scalar::Null, Scalar::Float));
+            }
+            if let Ok(value) = row.try_get::<Option<String>, _>(0) {
+                return Ok(value.map_or(Scalar::Null, Scalar::Text));
+            }
+            if let Ok(value) = row.try_get::<Option<bool>, _>(0) {
+                return Ok(value.map_or(Scalar::Null, Scalar::Bool));
+            }
+            bail!("Probe query returned a column of unsupported type")
+        }
+
+        /// Numbers compare by value; databases without a boolean type report 0/1.
+        fn matches(&self, expected: &ExpectedResult) -> bool {
+            match (self, expected) {
+                (Scalar::Integer(actual), ExpectedResult::Integer(wanted)) => actual == wanted,
+                (Scalar::Integer(actual), ExpectedResult::Float(wanted)) => *actual as f64 == *wanted,
+                (Scalar::Integer(actual), ExpectedResult::Bool(wanted)) => (*actual != 0) == *wanted,
+                (Scalar::Float(actual), ExpectedResult::Float(wanted)) => actual == wanted,
+                (Scalar::Float(actual), ExpectedResult::Integer(wanted)) => *actual == *wanted as f64,
+                (Scalar::Text(actual), ExpectedResult::Text(wanted)) => actual == wanted,
+                (Scalar::Bool(actual), ExpectedResult::Bool(wanted)) => actual == wanted,
+                _ => false,
+            }
+        }
+    }
+
+    impl Display for Scalar {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Scalar::Null => write!(f, "NULL"),
+                Scalar::Integer(value) => write!(f, "{}", value),
+                Scalar::Float(value) => write!(f, "{}", value),
+                Scalar::Text(value) => write!(f, "'{}'", value),
+                Scalar::Bool(value) => write!(f, "{}", value),
+            }
+        }
+    }
+
+    pub struct DbChecker {
+        name: String,
+        url: String,
+        query: String,
+        expect_result: Option<ExpectedResult>,
+    }
+
+    impl DbChecker {
+        pub fn new(
+            name: String,
+            url: String,
+            query: String,
+            expect_result: Option<ExpectedResult>,
+        ) -> Self {
+            install_default_drivers();
+            DbChecker {
+                name,
+                url,
+                query,
+                expect_result,
+            }
+        }
+
+        pub fn from_settings(
+            service: &ServiceSettings,
+            _global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            Ok(Arc::new(DbChecker::new(
+                service.name.clone(),
+                service.url.clone(),
+                service
+                    .query
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_PROBE_QUERY.to_string()),
+                service.expect_result.clone(),
+            )))
+        }
+
+        /// Connects with the driver matching the URL scheme and runs the probe query.
+        async fn probe(&self) -> Result<()> {
+            let mut conn = AnyConnection::connect(&self.url).await?;
+            let result = self.run_query(&mut conn).await;
+            conn.close().await?;
+            result
+        }
+
+        async fn run_query(&self, conn: &mut AnyConnection) -> Result<()> {
+            let Some(expected) = &self.expect_result else {
+                conn.execute(self.query.as_str()).await?;
+                return Ok(());
+            };
+            let Some(row) = conn.fetch_optional(self.query.as_str()).await? else {
+                bail!("Probe query returned no rows (expected {})", expected);
+            };
+            let actual = Scalar::from_row(&row)?;
+            if !actual.matches(expected) {
+                bail!("Probe query returned {} (expected {})", actual, expected);
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl HealthChecker for DbChecker {
+        async fn check(&self) -> HealthCheckStatus {
+            let start = Instant::now();
+            match self.probe().await {
+                Ok(()) => {
+                    let elapsed = start.elapsed().as_millis();
+                    info!("checked successful. name: {}, response time: {}ms", self.name, elapsed);
+                    HealthCheckStatus::healthy("OK", elapsed)
+                }
+                Err(e) => {
+                    error!("Database probe failed for '{}': {}", self.name, e);
+                    HealthCheckStatus::unhealthy(e.to_string(), start.elapsed().as_millis())
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,279 @@
+#[cfg(feature = "ssr")]
+pub mod dns {
+    use crate::checkers::HealthChecker;
+    use crate::types::{DnsRecordType, GlobalSettings, HealthCheckStatus, ServiceSettings};
+    use anyhow::{bail, Result};
+    use async_trait::async_trait;
+    use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig};
+    use hickory_resolver::name_server::TokioConnectionProvider;
+    use hickory_resolver::proto::rr::RecordType;
+    use hickory_resolver::{Resolver, TokioResolver};
+    use log::{error, info};
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    pub struct DnsChecker {
+        name: String,
+        query: String,
+        record_type: DnsRecordType,
+        resolver: TokioResolver,
+        expect_answers: Vec<String>,
+        min_answers: Option<usize>,
+    }
+
+    impl DnsChecker {
+        pub fn new(
+            name: String,
+            query: String,
+            record_type: DnsRecordType,
+            resolver: TokioResolver,
+            expect_answers: Vec<String>,
+            min_answers: Option<usize>,
+        ) -> Self {
+            DnsChecker {
+                name,
+                query,
+                record_type,
+                resolver,
+                expect_answers,
+                min_answers,
+            }
+        }
+
+        pub fn from_settings(
+            service: &ServiceSettings,
+            _global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            let resolver = build_resolver(service.resolver_addr()?)?;
+            Ok(Arc::new(DnsChecker::new(
+                service.name.clone(),
+                service.url.clone(),
+                service.record_type,
+                resolver,
+                service.expect_answers.clone(),
+                service.min_answers,
+            )))
+        }
+
+        /// Resolves the name and returns the answers in their presentation format.
+        async fn resolve(&self) -> Result<Vec<String>> {
+            let lookup = match self
+                .resolver
+                .lookup(self.query.as_str(), record_type(self.record_type))
+                .await
+            {
+                Ok(lookup) => lookup,
+                Err(e) if e.is_nx_domain() => bail!("{} does not exist (NXDOMAIN)", self.query),
+                Err(e) if e.is_no_records_found() => {
+                    bail!("No {} records for {}", self.record_type, self.query)
+                }
+                Err(e) => return Err(e.into()),
+            };
+            Ok(lookup.iter().map(|rdata| rdata.to_string()).collect())
+        }
+
+        fn verify_answers(&self, answers: &[String]) -> Result<()> {
+            if let Some(min) = self.min_answers {
+                if answers.len() < min {
+                    bail!(
+                        "Got {} {} records, expected at least {}",
+                        answers.len(),
+                        self.record_type,
+                        min
+                    );
+                }
+            }
+            let normalized: Vec<String> = answers.iter().map(|a| normalize(a)).collect();
+            for expected in &self.expect_answers {
+                if !normalized.contains(&normalize(expected)) {
+                    bail!(
+                        "Expected {} record '{}' not found in [{}]",
+                        self.record_type,
+                        expected,
+                        answers.join(", ")
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Uses the given nameserver, or the system configuration when none is set.
+    /// Caching is disabled so every check reaches the resolver.
+    fn build_resolver(nameserver: Option<SocketAddr>) -> Result<TokioResolver> {
+        let mut builder = match nameserver {
+            Some(addr) => {
+                let config = ResolverConfig::from_parts(
+                    None,
+                    vec![],
+                    NameServerConfigGroup::from_ips_clear(&[addr.ip()], addr.port(), true),
+                );
+                Resolver::builder_with_config(config, TokioConnectionProvider::default())
+            }
+            None => Resolver::builder_tokio()?,
+        };
+        builder.options_mut().cache_size = 0;
+        Ok(builder.build())
+    }
+
+    fn record_type(record_type: DnsRecordType) -> RecordType {
+        match record_type {
+            DnsRecordType::A => RecordType::A,
+            DnsRecordType::Aaaa => RecordType::AAAA,
+            DnsRecordType::Cname => RecordType::CNAME,
+            DnsRecordType::Mx => RecordType::MX,
+            DnsRecordType::Txt => RecordType::TXT,
+            DnsRecordType::Srv => RecordType::SRV,
+        }
+    }
+
+    /// Names compare case-insensitively and without the trailing root dot.
+    fn normalize(answer: &str) -> String {
+        answer.trim().trim_end_matches('.').to_lowercase()
+    }
+
+    #[async_trait]
+    impl HealthChecker for DnsChecker {
+        async fn check(&self) -> HealthCheckStatus {
+            let start = Instant::now();
+            let result = self.resolve().await;
+            let response_time = start.elapsed().as_millis();
+            match result.and_then(|answers| self.verify_answers(&answers).map(|_| answers)) {
+                Ok(answers) => {
+                    info!("checked successful. name: {}, response time: {}ms", self.name, response_time);
+                    HealthCheckStatus::healthy(
+                        format!(
+                            "Resolved {} {} records: {}",
+                            answers.len(),
+                            self.record_type,
+                            answers.join(", ")
+                        ),
+                        response_time,
+                    )
+                }
+                Err(e) => {
+                    error!("DNS lookup failed for '{}': {}", self.name, e);
+                    HealthCheckStatus::unhealthy(e.to_string(), response_time)
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::types::CheckStatus;
+        use hickory_resolver::proto::op::{Message, MessageType, ResponseCode};
+        use hickory_resolver::proto::rr::rdata::{A, CNAME};
+        use hickory_resolver::proto::rr::{Name, RData, Record};
+        use tokio::net::UdpSocket;
+
+        /// Answers queries for a small test zone over UDP, returning its address.
+        async fn responder() -> SocketAddr {
+            let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+            let address = socket.local_addr().unwrap();
+            tokio::spawn(async move {
+                let mut buffer = [0u8; 512];
+                loop {
+                    let (len, peer) = socket.recv_from(&mut buffer).await.unwrap();
+                    let request = Message::from_vec(&buffer[..len]).unwrap();
+                    let query = request.queries()[0].clone();
+                    let mut response = Message::new();
+                    response
+                        .set_id(request.id())
+                        .set_message_type(MessageType::Response)
+                        .set_op_code(request.op_code())
+                        .set_recursion_desired(request.recursion_desired())
+                        .set_recursion_available(true);
+                    let name = query.name().clone();
+                    match (name.to_ascii().as_str(), query.query_type()) {
+                        ("app.test.", RecordType::A) => {
+                            for last in [1, 2] {
+                                let rdata = RData::A(A::new(192, 0, 2, last));
+                                response.add_answer(Record::from_rdata(name.clone(), 60, rdata));
+                            }
+                        }
+                        ("www.test.", RecordType::CNAME) => {
+                            let target = Name::from_ascii("Edge.Example.NET.").unwrap();
+                            let rdata = RData::CNAME(CNAME(target));
+                            response.add_answer(Record::from_rdata(name.clone(), 60, rdata));
+                        }
+                        ("missing.test.", _) => {
+                            response.set_response_code(ResponseCode::NXDomain);
+                        }
+                        _ => {}
+                    }
+                    response.add_query(query);
+                    socket.send_to(&response.to_vec().unwrap(), peer).await.unwrap();
+                }
+            });
+            address
+        }
+
+        fn checker(
+            address: SocketAddr,
+            query: &str,
+            record_type: DnsRecordType,
+            expect_answers: &[&str],
+            min_answers: Option<usize>,
+        ) -> DnsChecker {
+            DnsChecker::new(
+                "dns".to_string(),
+                query.to_string(),
+                record_type,
+                build_resolver(Some(address)).unwrap(),
+                expect_answers.iter().map(|answer| answer.to_string()).collect(),
+                min_answers,
+            )
+        }
+
+        #[actix_rt::test]
+        async fn verifies_expected_answers_and_counts() {
+            let address = responder().await;
+
+            let status = checker(address, "app.test", DnsRecordType::A, &["192.0.2.2"], Some(2))
+                .check()
+                .await;
+            assert_eq!(status.status, CheckStatus::Healthy);
+            assert_eq!(status.status_message, "Resolved 2 A records: 192.0.2.1, 192.0.2.2");
+
+            let status = checker(address, "app.test", DnsRecordType::A, &[], Some(3)).check().await;
+            assert_eq!(status.status, CheckStatus::Unhealthy);
+            assert_eq!(status.status_message, "Got 2 A records, expected at least 3");
+
+            let status = checker(address, "app.test", DnsRecordType::A, &["192.0.2.9"], None)
+                .check()
+                .await;
+            assert_eq!(status.status, CheckStatus::Unhealthy);
+            assert_eq!(
+                status.status_message,
+                "Expected A record '192.0.2.9' not found in [192.0.2.1, 192.0.2.2]"
+            );
+        }
+
+        #[actix_rt::test]
+        async fn compares_names_without_case_or_trailing_dot() {
+            let address = responder().await;
+            for expected in ["edge.example.net", "EDGE.example.net."] {
+                let status = checker(address, "www.test", DnsRecordType::Cname, &[expected], None)
+                    .check()
+                    .await;
+                assert_eq!(status.status, CheckStatus::Healthy, "{}", status.status_message);
+            }
+        }
+
+        #[actix_rt::test]
+        async fn reports_missing_names_and_records() {
+            let address = responder().await;
+
+            let status = checker(address, "missing.test", DnsRecordType::A, &[], None).check().await;
+            assert_eq!(status.status, CheckStatus::Unhealthy);
+            assert_eq!(status.status_message, "missing.test does not exist (NXDOMAIN)");
+
+            let status = checker(address, "app.test", DnsRecordType::Mx, &[], None).check().await;
+            assert_eq!(status.status, CheckStatus::Unhealthy);
+            assert_eq!(status.status_message, "No MX records for app.test");
+        }
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "ssr")]
+pub mod grpc {
+    use crate::checkers::HealthChecker;
+    use crate::types::{CheckStatus, GlobalSettings, HealthCheckStatus, ServiceSettings};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use log::{error, info};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tonic::transport::{ClientTlsConfig, Endpoint};
+    use tonic::Code;
+    use tonic_health::pb::health_check_response::ServingStatus;
+    use tonic_health::pb::health_client::HealthClient;
+    use tonic_health::pb::HealthCheckRequest;
+
+    pub struct GrpcChecker {
+        name: String,
+        endpoint: Endpoint,
+        service: String,
+    }
+
+    impl GrpcChecker {
+        pub fn new(name: String, endpoint: Endpoint, service: String) -> Self {
+            GrpcChecker {
+                name,
+                endpoint,
+                service,
+            }
+        }
+
+        /// `https://` URLs use TLS with the system roots, `http://` URLs a plaintext channel.
+        pub fn from_settings(
+            service: &ServiceSettings,
+            global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            let timeout =
+                Duration::from_secs(service.timeout_seconds.unwrap_or(global.timeout_seconds));
+            let mut endpoint = Endpoint::from_shared(service.url.clone())?
+                .timeout(timeout)
+                .connect_timeout(timeout);
+            if service.url.starts_with("https://") {
+                endpoint = endpoint.tls_config(ClientTlsConfig::new().with_native_roots())?;
+            }
+            Ok(Arc::new(GrpcChecker::new(
+                service.name.clone(),
+                endpoint,
+                service.grpc_service.clone().unwrap_or_default(),
+            )))
+        }
+
+        /// Calls `grpc.health.v1.Health/Check` over a fresh channel.
+        async fn probe(&self) -> Result<ServingStatus, String> {
+            let channel = self.endpoint.connect().await.map_err(|e| {
+                let source = std::error::Error::source(&e)
+                    .map(|source| format!(": {}", source))
+                    .unwrap_or_default();
+                format!("{}{}", e, source)
+            })?;
+            let request = HealthCheckRequest {
+                service: self.service.clone(),
+            };
+            let response = HealthClient::new(channel)
+                .check(request)
+                .await
+                .map_err(|status| match status.code() {
+                    Code::NotFound => format!("Service '{}' is not registered", self.service),
+                    Code::Unimplemented => {
+                        "Server does not implement grpc.health.v1.Health".to_string()
+                    }
+                    code => format!("{:?}: {}", code, status.message()),
+                })?;
+            Ok(response.into_inner().status())
+        }
+
+        fn target(&self) -> &str {
+            if self.service.is_empty() {
+                "Server"
+            } else {
+                &self.service
+            }
+        }
+    }
+
+    fn check_status(serving: ServingStatus) -> CheckStatus {
+        match serving {
+            ServingStatus::Serving => CheckStatus::Healthy,
+            ServingStatus::Unknown => CheckStatus::Degraded,
+            ServingStatus::NotServing | ServingStatus::ServiceUnknown => CheckStatus::Unhealthy,
+        }
+    }
+
+    #[async_trait]
+    impl HealthChecker for GrpcChecker {
+        async fn check(&self) -> HealthCheckStatus {
+            let start = Instant::now();
+            match self.probe().await {
+                Ok(serving) => {
+                    let elapsed = start.elapsed().as_millis();
+                    info!("checked successful. name: {}, response time: {}ms", self.name, elapsed);
+                    HealthCheckStatus::new(
+                        check_status(serving),
+                        format!("{} is {}", self.target(), serving.as_str_name()),
+                        elapsed,
+                    )
+                }
+                Err(e) => {
+                    error!("gRPC health check failed for '{}': {}", self.name, e);
+                    HealthCheckStatus::unhealthy(e, start.elapsed().as_millis())
+                }
+            }
+        }
+    }
+}
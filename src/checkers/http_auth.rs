@@ -0,0 +1,116 @@
+#[cfg(feature = "ssr")]
+pub mod auth {
+    use crate::types::AuthSettings;
+    use anyhow::{anyhow, Result};
+    use log::info;
+    use reqwest::{Client, RequestBuilder};
+    use serde::Deserialize;
+    use std::time::{Duration, Instant};
+    use tokio::sync::Mutex;
+
+    /// Tokens are refreshed this long before they expire.
+    const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+    /// Lifetime assumed when the token endpoint omits `expires_in`.
+    const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(300);
+
+    #[derive(Deserialize)]
+    struct TokenResponse {
+        access_token: String,
+        expires_in: Option<u64>,
+    }
+
+    struct CachedToken {
+        access_token: String,
+        refresh_at: Instant,
+    }
+
+    /// Adds the configured credentials to HTTP check requests.
+    pub struct Authenticator {
+        settings: Option<AuthSettings>,
+        token: Mutex<Option<CachedToken>>,
+    }
+
+    impl Authenticator {
+        pub fn new(settings: Option<AuthSettings>) -> Self {
+            Authenticator {
+                settings,
+                token: Mutex::new(None),
+            }
+        }
+
+        pub async fn authorize(
+            &self,
+            client: &Client,
+            request: RequestBuilder,
+        ) -> Result<RequestBuilder> {
+            match &self.settings {
+                None => Ok(request),
+                Some(AuthSettings::Basic { username, password }) => {
+                    Ok(request.basic_auth(username, Some(password.resolve()?)))
+                }
+                Some(AuthSettings::Bearer { token }) => Ok(request.bearer_auth(token.resolve()?)),
+                Some(AuthSettings::Oauth2 { .. }) => {
+                    Ok(request.bearer_auth(self.access_token(client).await?))
+                }
+            }
+        }
+
+        /// Drops the cached token, e.g. after the server rejected it.
+        pub async fn invalidate(&self) {
+            self.token.lock().await.take();
+        }
+
+        async fn access_token(&self, client: &Client) -> Result<String> {
+            let mut cached = self.token.lock().await;
+            if let Some(token) = cached.as_ref().filter(|t| Instant::now() < t.refresh_at) {
+                return Ok(token.access_token.clone());
+            }
+            let token = self.fetch_token(client).await?;
+            let access_token = token.access_token.clone();
+            *cached = Some(token);
+            Ok(access_token)
+        }
+
+        async fn fetch_token(&self, client: &Client) -> Result<CachedToken> {
+            let Some(AuthSettings::Oauth2 {
+                token_url,
+                client_id,
+                client_secret,
+                scopes,
+            }) = &self.settings
+            else {
+                return Err(anyhow!("no OAuth2 settings"));
+            };
+            let scope = scopes.join(" ");
+            let mut form = vec![("grant_type", "client_credentials")];
+            if !scope.is_empty() {
+                form.push(("scope", scope.as_str()));
+            }
+            let response = client
+                .post(token_url)
+                .basic_auth(client_id, Some(client_secret.resolve()?))
+                .form(&form)
+                .send()
+                .await
+                .map_err(|e| anyhow!("OAuth2 token request failed: {}", e))?;
+            if !response.status().is_success() {
+                return Err(anyhow!(
+                    "OAuth2 token request failed with status {}",
+                    response.status()
+                ));
+            }
+            let token: TokenResponse = response
+                .json()
+                .await
+                .map_err(|e| anyhow!("OAuth2 token response is invalid: {}", e))?;
+            let lifetime = token
+                .expires_in
+                .map_or(DEFAULT_TOKEN_LIFETIME, Duration::from_secs);
+            info!("fetched OAuth2 token from {}, expires in {}s", token_url, lifetime.as_secs());
+            Ok(CachedToken {
+                access_token: token.access_token,
+                refresh_at: Instant::now() + lifetime.saturating_sub(REFRESH_MARGIN),
+            })
+        }
+    }
+}
@@ -0,0 +1,654 @@
+#[cfg(feature = "ssr")]
+pub mod http {
+    use crate::checkers::{
+        inspect_certificate, parse_target, Authenticator, HealthChecker, HttpTransport,
+    };
+    use crate::types::{
+        BodyAssertion, BodyRule, CertificateThresholds, CheckStatus, ExpectedStatus,
+        GlobalSettings, HealthCheckStatus, HttpRequestSettings, ServiceSettings, Timings,
+    };
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use log::{error, info, warn};
+    use regex::Regex;
+    use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, COOKIE, LOCATION, PROXY_AUTHORIZATION};
+    use reqwest::{Method, Request, Response, StatusCode, Url};
+    use serde_json::Value;
+    use serde_json_path::JsonPath;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    enum BodyCheck {
+        Contains(String),
+        NotContains(String),
+        Regex(Regex),
+        JsonPath {
+            path: JsonPath,
+            source: String,
+            equals: Option<Value>,
+        },
+    }
+
+    impl BodyCheck {
+        /// Returns why the body fails this check, if it does.
+        fn failure(&self, body: &str) -> Option<String> {
+            match self {
+                BodyCheck::Contains(value) if !body.contains(value.as_str()) => {
+                    Some(format!("Body does not contain '{}'", value))
+                }
+                BodyCheck::NotContains(value) if body.contains(value.as_str()) => {
+                    Some(format!("Body contains '{}'", value))
+                }
+                BodyCheck::Regex(pattern) if !pattern.is_match(body) => {
+                    Some(format!("Body does not match /{}/", pattern))
+                }
+                BodyCheck::Contains(_) | BodyCheck::NotContains(_) | BodyCheck::Regex(_) => None,
+                BodyCheck::JsonPath {
+                    path,
+                    source,
+                    equals,
+                } => {
+                    let document: Value = match serde_json::from_str(body) {
+                        Ok(document) => document,
+                        Err(e) => return Some(format!("Body is not valid JSON: {}", e)),
+                    };
+                    let Some(actual) = path.query(&document).first() else {
+                        return Some(format!("JSONPath {} matched nothing", source));
+                    };
+                    match equals {
+                        Some(expected) if actual != expected => Some(format!(
+                            "JSONPath {} is {} (expected {})",
+                            source, actual, expected
+                        )),
+                        _ => None,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compiled body assertions. The size limit is enforced while reading, before any
+    /// other assertion runs, since the body is not read past it.
+    #[derive(Default)]
+    pub struct BodyChecks {
+        max_size: Option<(usize, CheckStatus)>,
+        checks: Vec<(BodyCheck, CheckStatus)>,
+    }
+
+    impl BodyChecks {
+        pub fn compile(assertions: &[BodyAssertion]) -> Result<Self> {
+            let mut compiled = BodyChecks::default();
+            for assertion in assertions {
+                let check = match &assertion.rule {
+                    BodyRule::MaxSize { bytes } => {
+                        compiled
+                            .max_size
+                            .get_or_insert((*bytes, assertion.on_failure));
+                        continue;
+                    }
+                    BodyRule::Contains { value } => BodyCheck::Contains(value.clone()),
+                    BodyRule::NotContains { value } => BodyCheck::NotContains(value.clone()),
+                    BodyRule::Regex { pattern } => BodyCheck::Regex(Regex::new(pattern)?),
+                    BodyRule::JsonPath { path, equals } => BodyCheck::JsonPath {
+                        path: JsonPath::parse(path)?,
+                        source: path.clone(),
+                        equals: equals.clone(),
+                    },
+                };
+                compiled.checks.push((check, assertion.on_failure));
+            }
+            Ok(compiled)
+        }
+
+        fn is_empty(&self) -> bool {
+            self.max_size.is_none() && self.checks.is_empty()
+        }
+
+        /// Reads the body and returns the first failed assertion with its status.
+        async fn verify(&self, mut response: Response) -> Option<(CheckStatus, String)> {
+            let mut body = Vec::new();
+            loop {
+                match response.chunk().await {
+                    Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+                    Ok(None) => break,
+                    Err(e) => {
+                        return Some((
+                            CheckStatus::Unhealthy,
+                            format!("Failed to read body: {}", e),
+                        ))
+                    }
+                }
+                if let Some((limit, status)) = self.max_size {
+                    if body.len() > limit {
+                        return Some((status, format!("Body exceeds {} bytes", limit)));
+                    }
+                }
+            }
+            let body = String::from_utf8_lossy(&body);
+            self.checks.iter().find_map(|(check, status)| {
+                check.failure(&body).map(|message| (*status, message))
+            })
+        }
+    }
+
+    pub struct HttpChecker {
+        name: String,
+        url: String,
+        transport: HttpTransport,
+        expected_status: Option<ExpectedStatus>,
+        request: HttpRequestSettings,
+        authenticator: Authenticator,
+        certificate: Option<CertificateThresholds>,
+        body_checks: BodyChecks,
+    }
+
+    impl HttpChecker {
+        pub fn new(
+            name: String,
+            url: String,
+            transport: HttpTransport,
+            expected_status: Option<ExpectedStatus>,
+            request: HttpRequestSettings,
+            certificate: Option<CertificateThresholds>,
+            body_checks: BodyChecks,
+        ) -> Self {
+            HttpChecker {
+                name,
+                url,
+                transport,
+                expected_status,
+                authenticator: Authenticator::new(request.auth.clone()),
+                request,
+                certificate,
+                body_checks,
+            }
+        }
+
+        pub fn from_settings(
+            service: &ServiceSettings,
+            global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            Ok(Arc::new(HttpChecker::new(
+                service.name.clone(),
+                service.url.clone(),
+                HttpTransport::from_settings(service, global)?,
+                service.response_code.clone(),
+                service.http_request()?,
+                service
+                    .check_certificate
+                    .then(|| service.certificate_thresholds()),
+                BodyChecks::compile(&service.body_assertions)?,
+            )))
+        }
+
+        /// Without an explicit `response_code`, any 2xx status is considered healthy.
+        fn is_expected(&self, status: StatusCode) -> bool {
+            match &self.expected_status {
+                Some(expected) => expected.matches(status.as_u16()),
+                None => status.is_success(),
+            }
+        }
+
+        fn describe_expected(&self) -> String {
+            match &self.expected_status {
+                Some(expected) => expected.to_string(),
+                None => "2xx".to_string(),
+            }
+        }
+
+        /// Folds the certificate verdict into `status`, keeping the more severe of the two.
+        async fn check_certificate(
+            &self,
+            thresholds: &CertificateThresholds,
+            mut status: HealthCheckStatus,
+        ) -> HealthCheckStatus {
+            let inspection = match parse_target(&self.url) {
+                Ok((host, port)) => inspect_certificate(&host, port, self.transport.tls()).await,
+                Err(e) => Err(e),
+            };
+            let (certificate_status, certificate_message) = match inspection {
+                Ok(report) => report.evaluate(thresholds),
+                Err(e) => {
+                    warn!("Certificate inspection failed for '{}': {}", self.name, e);
+                    (CheckStatus::Unhealthy, format!("TLS handshake failed: {}", e))
+                }
+            };
+            status.status = status.status.max(certificate_status);
+            status.status_message = format!("{}; {}", status.status_message, certificate_message);
+            status
+        }
+
+        /// Builds one hop of the check. Credentials only go to the service's own origin.
+        async fn prepare(
+            &self,
+            method: &Method,
+            url: &Url,
+            with_body: bool,
+            same_origin: bool,
+        ) -> Result<Request> {
+            let mut headers = self.request.headers.clone();
+            if !with_body {
+                headers.remove(CONTENT_TYPE);
+            }
+            if !same_origin {
+                for name in [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION] {
+                    headers.remove(name);
+                }
+            }
+            let mut builder = self
+                .transport
+                .client()
+                .request(method.clone(), url.clone())
+                .headers(headers);
+            if let (true, Some(body)) = (with_body, &self.request.body) {
+                builder = builder.body(body.clone());
+            }
+            if same_origin {
+                builder = self
+                    .authenticator
+                    .authorize(self.transport.auxiliary_client(), builder)
+                    .await?;
+            }
+            Ok(builder.build()?)
+        }
+
+        /// Checks where the redirects led against `expect_location` and `expect_final_url`.
+        fn redirect_failure(&self, first_location: Option<&Url>, final_url: &Url) -> Option<String> {
+            let redirects = &self.request.redirects;
+            if let Some(expected) = &redirects.expect_location {
+                match first_location {
+                    Some(location) if location == expected => {}
+                    Some(location) => {
+                        return Some(format!("Redirected to {} (expected {})", location, expected))
+                    }
+                    None => return Some(format!("No redirect (expected {})", expected)),
+                }
+            }
+            match &redirects.expect_final_url {
+                Some(expected) if expected != final_url => {
+                    Some(format!("Ended at {} (expected {})", final_url, expected))
+                }
+                _ => None,
+            }
+        }
+
+        async fn request(&self) -> HealthCheckStatus {
+            let mut url = match Url::parse(&self.url) {
+                Ok(url) => url,
+                Err(e) => {
+                    return HealthCheckStatus::unhealthy(
+                        format!("Invalid url '{}': {}", self.url, e),
+                        0,
+                    )
+                }
+            };
+            let origin = url.origin();
+            let mut method = self.request.method.clone();
+            let mut with_body = true;
+            let mut chain = vec![url.to_string()];
+            let mut first_location = None;
+            let mut timings = None;
+
+            // Token fetches are not part of the measured response time.
+            let mut request = match self.prepare(&method, &url, with_body, true).await {
+                Ok(request) => request,
+                Err(e) => {
+                    error!("Failed to prepare request for '{}': {}", self.name, e);
+                    return HealthCheckStatus::unhealthy(e.to_string(), 0);
+                }
+            };
+
+            let start = Instant::now();
+            let response = loop {
+                let response = match self.transport.send(request).await {
+                    Ok((response, hop_timings)) => {
+                        if let Some(hop_timings) = hop_timings {
+                            *timings.get_or_insert_with(Timings::default) += hop_timings;
+                        }
+                        response
+                    }
+                    Err(e) => {
+                        // The alternate format includes the causes, e.g. the TLS alert behind
+                        // reqwest's generic "error sending request".
+                        let message = with_chain(format!("{:#}", e), &chain);
+                        error!("Request failed for '{}': {}", self.name, message);
+                        return HealthCheckStatus::unhealthy(message, start.elapsed().as_millis());
+                    }
+                };
+                if response.status() == StatusCode::UNAUTHORIZED {
+                    self.authenticator.invalidate().await;
+                }
+                let Some(location) = redirect_target(&url, &response) else {
+                    break response;
+                };
+                first_location.get_or_insert_with(|| location.clone());
+                let max_hops = self.request.redirects.max_hops;
+                if max_hops == 0 {
+                    break response;
+                }
+                if chain.len() > max_hops {
+                    chain.push(location.to_string());
+                    let message =
+                        with_chain(format!("Too many redirects (max {})", max_hops), &chain);
+                    warn!("Redirect limit reached for '{}': {}", self.name, message);
+                    return HealthCheckStatus::unhealthy(message, start.elapsed().as_millis())
+                        .with_timings(timings);
+                }
+
+                // Like browsers, 303 and a POST answered with 301/302 continue as a GET.
+                let status = response.status();
+                if status == StatusCode::SEE_OTHER
+                    || (method == Method::POST
+                        && matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND))
+                {
+                    if method != Method::HEAD {
+                        method = Method::GET;
+                    }
+                    with_body = false;
+                }
+                url = location;
+                chain.push(url.to_string());
+                request = match self
+                    .prepare(&method, &url, with_body, url.origin() == origin)
+                    .await
+                {
+                    Ok(request) => request,
+                    Err(e) => {
+                        let message = with_chain(e.to_string(), &chain);
+                        error!("Failed to follow redirect for '{}': {}", self.name, message);
+                        return HealthCheckStatus::unhealthy(message, start.elapsed().as_millis())
+                            .with_timings(timings);
+                    }
+                };
+            };
+
+            let status = response.status();
+            if !self.is_expected(status) {
+                let message = with_chain(
+                    format!(
+                        "Unexpected status {} (expected {})",
+                        status,
+                        self.describe_expected()
+                    ),
+                    &chain,
+                );
+                warn!("Unexpected HTTP status for '{}': {}", self.name, message);
+                return HealthCheckStatus::unhealthy(message, start.elapsed().as_millis())
+                    .with_timings(timings);
+            }
+            if let Some(message) = self.redirect_failure(first_location.as_ref(), &url) {
+                let message = with_chain(message, &chain);
+                warn!("Redirect assertion failed for '{}': {}", self.name, message);
+                return HealthCheckStatus::unhealthy(message, start.elapsed().as_millis())
+                    .with_timings(timings);
+            }
+            if !self.body_checks.is_empty() {
+                if let Some((failed_status, message)) = self.body_checks.verify(response).await {
+                    let message = with_chain(message, &chain);
+                    warn!("Body assertion failed for '{}': {}", self.name, message);
+                    return HealthCheckStatus::new(failed_status, message, start.elapsed().as_millis())
+                        .with_timings(timings);
+                }
+            }
+            let elapsed = start.elapsed().as_millis();
+            info!("checked successful. name: {}, response time: {}ms", self.name, elapsed);
+            HealthCheckStatus::healthy(status.canonical_reason().unwrap_or("OK"), elapsed)
+                .with_timings(timings)
+        }
+    }
+
+    /// Resolves the `Location` of a 3xx response against the URL that produced it.
+    fn redirect_target(url: &Url, response: &Response) -> Option<Url> {
+        if !response.status().is_redirection() {
+            return None;
+        }
+        let location = response.headers().get(LOCATION)?.to_str().ok()?;
+        url.join(location).ok()
+    }
+
+    fn with_chain(message: String, chain: &[String]) -> String {
+        if chain.len() > 1 {
+            format!("{} (redirects: {})", message, chain.join(" -> "))
+        } else {
+            message
+        }
+    }
+
+    #[async_trait]
+    impl HealthChecker for HttpChecker {
+        async fn check(&self) -> HealthCheckStatus {
+            let status = self.request().await;
+            match &self.certificate {
+                Some(thresholds) => self.check_certificate(thresholds, status).await,
+                None => status,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use serde_json::json;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        fn body_checks(assertions: Value) -> BodyChecks {
+            let assertions: Vec<BodyAssertion> = serde_json::from_value(assertions).unwrap();
+            BodyChecks::compile(&assertions).unwrap()
+        }
+
+        async fn verify(checks: &BodyChecks, body: &str) -> Option<(CheckStatus, String)> {
+            checks
+                .verify(Response::from(http::Response::new(body.to_string())))
+                .await
+        }
+
+        #[actix_rt::test]
+        async fn passes_when_every_assertion_holds() {
+            let checks = body_checks(json!([
+                { "type": "contains", "value": "UP" },
+                { "type": "not_contains", "value": "DOWN" },
+                { "type": "regex", "pattern": "\"version\":\\s*\"\\d+" },
+                { "type": "json_path", "path": "$.status", "equals": "UP" },
+                { "type": "max_size", "bytes": 64 },
+            ]));
+            assert_eq!(verify(&checks, r#"{"status": "UP", "version": "3"}"#).await, None);
+        }
+
+        #[actix_rt::test]
+        async fn reports_the_first_failure_with_its_status() {
+            let checks = body_checks(json!([
+                { "type": "not_contains", "value": "OUT_OF_SERVICE", "on_failure": "Degraded" },
+                { "type": "contains", "value": "UP" },
+            ]));
+            assert_eq!(
+                verify(&checks, "OUT_OF_SERVICE").await,
+                Some((CheckStatus::Degraded, "Body contains 'OUT_OF_SERVICE'".to_string()))
+            );
+        }
+
+        #[actix_rt::test]
+        async fn checks_the_size_before_anything_else() {
+            let checks = body_checks(json!([
+                { "type": "contains", "value": "missing" },
+                { "type": "max_size", "bytes": 4 },
+            ]));
+            assert_eq!(
+                verify(&checks, "too long").await,
+                Some((CheckStatus::Unhealthy, "Body exceeds 4 bytes".to_string()))
+            );
+        }
+
+        #[actix_rt::test]
+        async fn explains_json_path_failures() {
+            let checks = body_checks(json!([{ "type": "json_path", "path": "$.status" }]));
+            let (_, message) = verify(&checks, "<html>").await.unwrap();
+            assert!(message.starts_with("Body is not valid JSON"), "{}", message);
+            assert_eq!(
+                verify(&checks, "{}").await,
+                Some((CheckStatus::Unhealthy, "JSONPath $.status matched nothing".to_string()))
+            );
+
+            let checks =
+                body_checks(json!([{ "type": "json_path", "path": "$.status", "equals": "UP" }]));
+            assert_eq!(
+                verify(&checks, r#"{"status": "DOWN"}"#).await,
+                Some((
+                    CheckStatus::Unhealthy,
+                    r#"JSONPath $.status is "DOWN" (expected "UP")"#.to_string()
+                ))
+            );
+        }
+
+        /// Serves `routes` of `(path, "status line\r\nheaders")` on a local port; every response
+        /// body echoes the method and path of the request. Returns the base URL.
+        async fn serve(routes: &'static [(&'static str, &'static str)]) -> String {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let base = format!("http://{}", listener.local_addr().unwrap());
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let mut request = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match stream.read(&mut chunk).await {
+                            Ok(0) | Err(_) => break,
+                            Ok(read) => request.extend_from_slice(&chunk[..read]),
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&request).to_string();
+                    let mut words = request.split_whitespace();
+                    let (method, path) = (words.next().unwrap_or(""), words.next().unwrap_or(""));
+                    let head = routes
+                        .iter()
+                        .find(|(route, _)| *route == path)
+                        .map_or("404 Not Found", |(_, head)| *head);
+                    let body = format!("{} {}", method, path);
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        head,
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                }
+            });
+            base
+        }
+
+        async fn check(base: &str, settings: &str) -> HealthCheckStatus {
+            let settings = settings.replace("{base}", base);
+            let service: ServiceSettings =
+                toml::from_str(&format!("name = \"web\"\ntype = \"http\"\n{}", settings)).unwrap();
+            let global = GlobalSettings::default();
+            service.validate(&global).unwrap();
+            HttpChecker::from_settings(&service, &global).unwrap().check().await
+        }
+
+        static CHAIN: &[(&str, &str)] = &[
+            ("/a", "301 Moved Permanently\r\nLocation: /b"),
+            ("/b", "302 Found\r\nLocation: /c"),
+            ("/c", "200 OK"),
+        ];
+
+        #[actix_rt::test]
+        async fn follows_redirects_to_the_final_url() {
+            let base = serve(CHAIN).await;
+            let status = check(
+                &base,
+                r#"
+                url = "{base}/a"
+                expect_final_url = "{base}/c"
+                body_assertions = [{ type = "contains", value = "GET /c" }]
+                "#,
+            )
+            .await;
+            assert_eq!(status.status, CheckStatus::Healthy, "{}", status.status_message);
+        }
+
+        #[actix_rt::test]
+        async fn reports_the_chain_when_the_final_url_differs() {
+            let base = serve(CHAIN).await;
+            let status = check(
+                &base,
+                r#"
+                url = "{base}/a"
+                expect_final_url = "{base}/b"
+                "#,
+            )
+            .await;
+            assert_eq!(status.status, CheckStatus::Unhealthy);
+            assert_eq!(
+                status.status_message,
+                format!(
+                    "Ended at {0}/c (expected {0}/b) (redirects: {0}/a -> {0}/b -> {0}/c)",
+                    base
+                )
+            );
+        }
+
+        #[actix_rt::test]
+        async fn stops_at_the_hop_limit() {
+            static LOOP: &[(&str, &str)] = &[
+                ("/one", "302 Found\r\nLocation: /two"),
+                ("/two", "302 Found\r\nLocation: /one"),
+            ];
+            let base = serve(LOOP).await;
+            let status = check(
+                &base,
+                r#"
+                url = "{base}/one"
+                follow_redirects = 2
+                "#,
+            )
+            .await;
+            assert_eq!(
+                status.status_message,
+                format!(
+                    "Too many redirects (max 2) (redirects: {0}/one -> {0}/two -> {0}/one -> {0}/two)",
+                    base
+                )
+            );
+        }
+
+        #[actix_rt::test]
+        async fn checks_the_location_when_not_following() {
+            static LOGIN: &[(&str, &str)] =
+                &[("/admin", "302 Found\r\nLocation: /login?next=/admin")];
+            let base = serve(LOGIN).await;
+            let settings = r#"
+                url = "{base}/admin"
+                follow_redirects = false
+                response_code = 302
+                "#;
+            let expected = "expect_location = \"/login?next=/admin\"";
+            let status = check(&base, &format!("{}{}", settings, expected)).await;
+            assert_eq!(status.status, CheckStatus::Healthy, "{}", status.status_message);
+
+            let status = check(&base, &format!("{}expect_location = \"/signin\"", settings)).await;
+            assert_eq!(
+                status.status_message,
+                format!("Redirected to {0}/login?next=/admin (expected {0}/signin)", base)
+            );
+        }
+
+        #[actix_rt::test]
+        async fn continues_a_post_as_get_after_see_other() {
+            static FORM: &[(&str, &str)] = &[
+                ("/form", "303 See Other\r\nLocation: /done"),
+                ("/done", "200 OK"),
+            ];
+            let base = serve(FORM).await;
+            let status = check(
+                &base,
+                r#"
+                url = "{base}/form"
+                method = "POST"
+                body = "x=1"
+                body_assertions = [{ type = "contains", value = "GET /done" }]
+                "#,
+            )
+            .await;
+            assert_eq!(status.status, CheckStatus::Healthy, "{}", status.status_message);
+        }
+    }
+}
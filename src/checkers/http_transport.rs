@@ -0,0 +1,324 @@
+#[cfg(feature = "ssr")]
+pub mod transport {
+    use crate::checkers::client_tls_config;
+    use crate::types::{BodyAssertion, GlobalSettings, ServiceSettings, Timings, TlsSettings};
+    use anyhow::{anyhow, bail, Context, Result};
+    use http_body_util::BodyExt;
+    use hyper::body::Bytes;
+    use hyper::client::conn::http1;
+    use hyper_util::client::proxy::matcher::Matcher;
+    use hyper_util::rt::TokioIo;
+    use reqwest::header::HOST;
+    use reqwest::redirect::Policy;
+    use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+    use reqwest::{Client, Request, Response, Url};
+    use rustls::pki_types::ServerName;
+    use rustls::client::Resumption;
+    use rustls::ClientConfig;
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+    use tokio::io::{AsyncRead, AsyncWrite};
+    use tokio::net::TcpStream;
+    use tokio_rustls::TlsConnector;
+
+    /// Sends `server_name` as SNI and Host while connecting to the host from the URL.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct PinnedHost {
+        server_name: String,
+        address: String,
+    }
+
+    impl PinnedHost {
+        /// The host to resolve and connect to for a request addressed to `host`.
+        fn target<'a>(&'a self, host: &'a str) -> &'a str {
+            if host == self.server_name {
+                &self.address
+            } else {
+                host
+            }
+        }
+    }
+
+    impl Resolve for PinnedHost {
+        fn resolve(&self, name: Name) -> Resolving {
+            let host = self.target(name.as_str()).to_string();
+            Box::pin(async move {
+                let addresses: Vec<SocketAddr> =
+                    tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+                Ok(Box::new(addresses.into_iter()) as Addrs)
+            })
+        }
+    }
+
+    /// Everything that makes one HTTP client differ from another. Services with equal keys
+    /// share a client, and with it the connection pool and TLS sessions.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct ClientKey {
+        timeout_seconds: u64,
+        tls: TlsSettings,
+        pinned: Option<PinnedHost>,
+    }
+
+    impl ClientKey {
+        /// A `fresh` client keeps no idle connections and resumes no TLS sessions, so every
+        /// request pays for a full connection setup.
+        fn build(&self, fresh: bool) -> Result<Client> {
+            // Redirects are followed by the checker itself so it can record the chain.
+            let mut builder = reqwest::ClientBuilder::new()
+                .timeout(Duration::from_secs(self.timeout_seconds))
+                .redirect(Policy::none());
+            if fresh {
+                builder = builder.pool_max_idle_per_host(0);
+            }
+            // Every probe goes through rustls, whether or not the service configures TLS,
+            // so results do not depend on which backend a service happens to get.
+            let mut config = client_tls_config(&self.tls)?;
+            if fresh {
+                config.resumption = Resumption::disabled();
+            }
+            builder = builder.use_preconfigured_tls(config);
+            if let Some(pinned) = &self.pinned {
+                builder = builder.dns_resolver(Arc::new(pinned.clone()));
+            }
+            Ok(builder.build()?)
+        }
+    }
+
+    fn shared_client(key: &ClientKey) -> Result<Client> {
+        static CLIENTS: OnceLock<Mutex<HashMap<ClientKey, Client>>> = OnceLock::new();
+        let mut clients = CLIENTS.get_or_init(Default::default).lock().unwrap();
+        if let Some(client) = clients.get(key) {
+            return Ok(client.clone());
+        }
+        let client = key.build(false)?;
+        clients.insert(key.clone(), client.clone());
+        Ok(client)
+    }
+
+    /// Sends HTTP check requests, either through a pooled reqwest client or, when timings
+    /// are recorded, over a new connection whose phases are measured one by one.
+    pub struct HttpTransport {
+        client: Client,
+        /// Client with default TLS settings for side requests such as OAuth2 token fetches,
+        /// which go to identity providers that the service's private CA and client certificate
+        /// are not for.
+        auxiliary: Client,
+        tls: TlsSettings,
+        pinned: Option<PinnedHost>,
+        timed: Option<Arc<ClientConfig>>,
+        /// Body bytes after which a timed exchange stops reading.
+        body_limit: Option<usize>,
+    }
+
+    impl HttpTransport {
+        pub fn from_settings(service: &ServiceSettings, global: &GlobalSettings) -> Result<Self> {
+            let pinned = match service.tls.as_ref().and_then(|tls| tls.server_name.clone()) {
+                Some(server_name) => Some(PinnedHost {
+                    server_name,
+                    address: url_host(&Url::parse(&service.url)?)?,
+                }),
+                None => None,
+            };
+            let tls = service.tls.clone().unwrap_or_default();
+            let key = ClientKey {
+                timeout_seconds: service.timeout_seconds.unwrap_or(global.timeout_seconds),
+                tls: tls.clone(),
+                pinned: pinned.clone(),
+            };
+            let client = if service.fresh_connection {
+                key.build(true)?
+            } else {
+                shared_client(&key)?
+            };
+            let auxiliary = shared_client(&ClientKey {
+                timeout_seconds: key.timeout_seconds,
+                tls: TlsSettings::default(),
+                pinned: None,
+            })?;
+            let timed = if service.record_timings {
+                if Matcher::from_system()
+                    .intercept(&service.url.parse::<http::Uri>()?)
+                    .is_some()
+                {
+                    bail!(
+                        "service '{}': record_timings cannot measure requests through a proxy; add the host to NO_PROXY",
+                        service.name
+                    );
+                }
+                // Without resumption every check times a full handshake.
+                let mut config = client_tls_config(&tls)?;
+                config.resumption = Resumption::disabled();
+                Some(Arc::new(config))
+            } else {
+                None
+            };
+            Ok(HttpTransport {
+                client,
+                auxiliary,
+                tls,
+                pinned,
+                timed,
+                body_limit: BodyAssertion::size_limit(&service.body_assertions),
+            })
+        }
+
+        /// The underlying client, for building check requests.
+        pub fn client(&self) -> &Client {
+            &self.client
+        }
+
+        /// A client with the system roots and no client certificate.
+        pub fn auxiliary_client(&self) -> &Client {
+            &self.auxiliary
+        }
+
+        /// The service's TLS settings, defaults when it has none.
+        pub fn tls(&self) -> &TlsSettings {
+            &self.tls
+        }
+
+        pub async fn send(&self, mut request: Request) -> Result<(Response, Option<Timings>)> {
+            if let Some(pinned) = &self.pinned {
+                if url_host(request.url())? == pinned.address {
+                    request
+                        .url_mut()
+                        .set_host(Some(&pinned.server_name))
+                        .context("invalid server_name")?;
+                }
+            }
+            match &self.timed {
+                Some(tls) => {
+                    let (response, timings) =
+                        send_timed(request, tls.clone(), self.pinned.as_ref(), self.body_limit)
+                            .await?;
+                    Ok((response, Some(timings)))
+                }
+                None => Ok((self.client.execute(request).await?, None)),
+            }
+        }
+    }
+
+    /// The URL's host without the brackets around IPv6 addresses.
+    fn url_host(url: &Url) -> Result<String> {
+        Ok(url
+            .host_str()
+            .ok_or_else(|| anyhow!("URL has no host"))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string())
+    }
+
+    /// Resolves, connects, handshakes and exchanges the request step by step.
+    /// The body is read up front, up to one byte past `body_limit`, so that the transfer
+    /// time can be reported and an oversized body still fails its `max_size` assertion.
+    async fn send_timed(
+        request: Request,
+        tls: Arc<ClientConfig>,
+        pinned: Option<&PinnedHost>,
+        body_limit: Option<usize>,
+    ) -> Result<(Response, Timings)> {
+        let url = request.url().clone();
+        let host = url_host(&url)?;
+        let target = pinned.map_or(host.as_str(), |pinned| pinned.target(&host));
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| anyhow!("URL has no port"))?;
+
+        let start = Instant::now();
+        let addresses: Vec<SocketAddr> = tokio::net::lookup_host((target, port))
+            .await
+            .with_context(|| format!("DNS lookup for {} failed", target))?
+            .collect();
+        let dns = start.elapsed().as_millis();
+
+        let start = Instant::now();
+        let tcp = connect(&addresses).await?;
+        let connect = start.elapsed().as_millis();
+
+        let mut http_request: http::Request<reqwest::Body> = request.try_into()?;
+        if !http_request.headers().contains_key(HOST) {
+            let authority = match url.port() {
+                Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+                None => url.host_str().unwrap_or_default().to_string(),
+            };
+            http_request.headers_mut().insert(HOST, authority.parse()?);
+        }
+        let origin_form = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        *http_request.uri_mut() = origin_form.parse()?;
+
+        let (response, tls, ttfb, transfer) = if url.scheme() == "https" {
+            let start = Instant::now();
+            let stream = TlsConnector::from(tls)
+                .connect(ServerName::try_from(host)?, tcp)
+                .await
+                .context("TLS handshake failed")?;
+            let handshake = start.elapsed().as_millis();
+            let (response, ttfb, transfer) = exchange(stream, http_request, body_limit).await?;
+            (response, Some(handshake), ttfb, transfer)
+        } else {
+            let (response, ttfb, transfer) = exchange(tcp, http_request, body_limit).await?;
+            (response, None, ttfb, transfer)
+        };
+
+        Ok((
+            Response::from(response),
+            Timings {
+                dns,
+                connect,
+                tls,
+                ttfb,
+                transfer,
+            },
+        ))
+    }
+
+    async fn connect(addresses: &[SocketAddr]) -> Result<TcpStream> {
+        let mut last_error = None;
+        for address in addresses {
+            match TcpStream::connect(address).await {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_error = Some(anyhow!("connect to {} failed: {}", address, e)),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow!("DNS lookup returned no addresses")))
+    }
+
+    /// Returns the response with its body read, the time to first byte and the transfer time.
+    async fn exchange<S>(
+        stream: S,
+        request: http::Request<reqwest::Body>,
+        body_limit: Option<usize>,
+    ) -> Result<(http::Response<Bytes>, u128, u128)>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sender, connection) = http1::handshake(TokioIo::new(stream)).await?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+
+        let start = Instant::now();
+        let response = sender.send_request(request).await?;
+        let ttfb = start.elapsed().as_millis();
+
+        let start = Instant::now();
+        let (parts, mut body) = response.into_parts();
+        let mut collected = Vec::new();
+        while let Some(frame) = body.frame().await {
+            if let Ok(data) = frame?.into_data() {
+                collected.extend_from_slice(&data);
+                if body_limit.is_some_and(|limit| collected.len() > limit) {
+                    break;
+                }
+            }
+        }
+        let body = Bytes::from(collected);
+        let transfer = start.elapsed().as_millis();
+        Ok((http::Response::from_parts(parts, body), ttfb, transfer))
+    }
+}
@@ -0,0 +1,41 @@
+#[cfg(feature = "ssr")]
+mod checker;
+#[cfg(feature = "ssr")]
+mod db_checker;
+#[cfg(feature = "ssr")]
+mod dns_checker;
+#[cfg(feature = "ssr")]
+mod grpc_checker;
+#[cfg(feature = "ssr")]
+mod http_auth;
+#[cfg(feature = "ssr")]
+mod http_checker;
+#[cfg(feature = "ssr")]
+mod http_transport;
+#[cfg(feature = "ssr")]
+mod redis_checker;
+#[cfg(feature = "ssr")]
+mod tcp_checker;
+#[cfg(feature = "ssr")]
+mod tls_checker;
+
+#[cfg(feature = "ssr")]
+pub use checker::health_checker::*;
+#[cfg(feature = "ssr")]
+pub use db_checker::db::*;
+#[cfg(feature = "ssr")]
+pub use dns_checker::dns::*;
+#[cfg(feature = "ssr")]
+pub use grpc_checker::grpc::*;
+#[cfg(feature = "ssr")]
+pub use http_auth::auth::*;
+#[cfg(feature = "ssr")]
+pub use http_checker::http::*;
+#[cfg(feature = "ssr")]
+pub use http_transport::transport::*;
+#[cfg(feature = "ssr")]
+pub use redis_checker::redis::*;
+#[cfg(feature = "ssr")]
+pub use tcp_checker::tcp::*;
+#[cfg(feature = "ssr")]
+pub use tls_checker::tls::*;
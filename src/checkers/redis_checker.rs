@@ -0,0 +1,258 @@
+#[cfg(feature = "ssr")]
+pub mod redis {
+    use crate::checkers::HealthChecker;
+    use crate::types::{CheckStatus, GlobalSettings, HealthCheckStatus, ServiceSettings};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use log::{error, info};
+    use redis::Client;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    pub const DEFAULT_MAX_REPLICATION_LAG_SECONDS: u64 = 10;
+
+    pub struct RedisChecker {
+        name: String,
+        client: Client,
+        check_replication: bool,
+        max_replication_lag_seconds: u64,
+    }
+
+    /// Replication state as reported by `INFO replication`.
+    #[derive(Debug, Default)]
+    struct ReplicationInfo {
+        role: String,
+        master_link_up: Option<bool>,
+        master_last_io_seconds_ago: Option<u64>,
+        replicas: Vec<ReplicaInfo>,
+    }
+
+    #[derive(Debug)]
+    struct ReplicaInfo {
+        address: String,
+        online: bool,
+        lag: Option<u64>,
+    }
+
+    impl ReplicationInfo {
+        fn parse(info: &str) -> Self {
+            let mut parsed = ReplicationInfo::default();
+            for line in info.lines() {
+                let Some((key, value)) = line.trim().split_once(':') else {
+                    continue;
+                };
+                match key {
+                    "role" => parsed.role = value.to_string(),
+                    "master_link_status" => parsed.master_link_up = Some(value == "up"),
+                    "master_last_io_seconds_ago" => {
+                        parsed.master_last_io_seconds_ago = value.parse().ok()
+                    }
+                    key if key.starts_with("slave") && key[5..].parse::<u32>().is_ok() => {
+                        parsed.replicas.push(ReplicaInfo::parse(value))
+                    }
+                    _ => {}
+                }
+            }
+            parsed
+        }
+
+        /// Returns `Degraded` with a reason when a link is down or lagging.
+        fn evaluate(&self, max_lag: u64) -> (CheckStatus, String) {
+            if self.role == "slave" {
+                if self.master_link_up == Some(false) {
+                    return (CheckStatus::Degraded, "replica link to master is down".to_string());
+                }
+                if let Some(seconds) = self.master_last_io_seconds_ago.filter(|s| *s > max_lag) {
+                    return (
+                        CheckStatus::Degraded,
+                        format!("no data from master for {}s (max {}s)", seconds, max_lag),
+                    );
+                }
+                return (CheckStatus::Healthy, "role replica, link up".to_string());
+            }
+            for replica in &self.replicas {
+                if !replica.online {
+                    return (
+                        CheckStatus::Degraded,
+                        format!("replica {} is not online", replica.address),
+                    );
+                }
+                if let Some(lag) = replica.lag.filter(|lag| *lag > max_lag) {
+                    return (
+                        CheckStatus::Degraded,
+                        format!("replica {} lags {}s (max {}s)", replica.address, lag, max_lag),
+                    );
+                }
+            }
+            (
+                CheckStatus::Healthy,
+                format!("role {}, {} replicas", self.role, self.replicas.len()),
+            )
+        }
+    }
+
+    impl ReplicaInfo {
+        /// Parses `ip=10.0.0.2,port=6379,state=online,offset=123,lag=0`.
+        fn parse(value: &str) -> Self {
+            let field = |name: &str| {
+                value
+                    .split(',')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value)
+            };
+            ReplicaInfo {
+                address: format!(
+                    "{}:{}",
+                    field("ip").unwrap_or("?"),
+                    field("port").unwrap_or("?")
+                ),
+                online: field("state") == Some("online"),
+                lag: field("lag").and_then(|lag| lag.parse().ok()),
+            }
+        }
+    }
+
+    impl RedisChecker {
+        pub fn new(
+            name: String,
+            client: Client,
+            check_replication: bool,
+            max_replication_lag_seconds: u64,
+        ) -> Self {
+            RedisChecker {
+                name,
+                client,
+                check_replication,
+                max_replication_lag_seconds,
+            }
+        }
+
+        pub fn from_settings(
+            service: &ServiceSettings,
+            _global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            Ok(Arc::new(RedisChecker::new(
+                service.name.clone(),
+                Client::open(service.url.as_str())?,
+                service.check_replication,
+                service
+                    .max_replication_lag_seconds
+                    .unwrap_or(DEFAULT_MAX_REPLICATION_LAG_SECONDS),
+            )))
+        }
+
+        /// Sends `PING` and, when enabled, reads `INFO replication`.
+        async fn probe(&self) -> Result<(u128, Option<ReplicationInfo>), redis::RedisError> {
+            let start = Instant::now();
+            let mut conn = self.client.get_multiplexed_async_connection().await?;
+            redis::cmd("PING").query_async::<String>(&mut conn).await?;
+            let ping_time = start.elapsed().as_millis();
+            if !self.check_replication {
+                return Ok((ping_time, None));
+            }
+            let info: String = redis::cmd("INFO")
+                .arg("replication")
+                .query_async(&mut conn)
+                .await?;
+            Ok((ping_time, Some(ReplicationInfo::parse(&info))))
+        }
+    }
+
+    #[async_trait]
+    impl HealthChecker for RedisChecker {
+        async fn check(&self) -> HealthCheckStatus {
+            let start = Instant::now();
+            match self.probe().await {
+                Ok((ping_time, replication)) => {
+                    info!("checked successful. name: {}, response time: {}ms", self.name, ping_time);
+                    let (status, status_message) = match replication {
+                        Some(replication) => {
+                            let (status, detail) =
+                                replication.evaluate(self.max_replication_lag_seconds);
+                            (status, format!("PONG in {}ms; {}", ping_time, detail))
+                        }
+                        None => (CheckStatus::Healthy, format!("PONG in {}ms", ping_time)),
+                    };
+                    HealthCheckStatus::new(status, status_message, ping_time)
+                }
+                Err(e) => {
+                    error!("Redis probe failed for '{}': {}", self.name, e);
+                    HealthCheckStatus::unhealthy(e.to_string(), start.elapsed().as_millis())
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        const MASTER: &str = "# Replication\r\n\
+            role:master\r\n\
+            connected_slaves:2\r\n\
+            slave0:ip=10.0.0.2,port=6379,state=online,offset=4242,lag=0\r\n\
+            slave1:ip=10.0.0.3,port=6380,state=online,offset=4200,lag=3\r\n\
+            master_repl_offset:4242\r\n";
+
+        const REPLICA: &str = "# Replication\r\n\
+            role:slave\r\n\
+            master_host:10.0.0.1\r\n\
+            master_link_status:up\r\n\
+            master_last_io_seconds_ago:1\r\n";
+
+        #[test]
+        fn parses_master_with_replicas() {
+            let info = ReplicationInfo::parse(MASTER);
+            assert_eq!(info.role, "master");
+            assert_eq!(info.replicas.len(), 2);
+            assert_eq!(info.replicas[1].address, "10.0.0.3:6380");
+            assert!(info.replicas[1].online);
+            assert_eq!(info.replicas[1].lag, Some(3));
+            assert_eq!(
+                info.evaluate(10),
+                (CheckStatus::Healthy, "role master, 2 replicas".to_string())
+            );
+        }
+
+        #[test]
+        fn degrades_on_lagging_or_offline_replicas() {
+            let info = ReplicationInfo::parse(MASTER);
+            assert_eq!(
+                info.evaluate(2),
+                (CheckStatus::Degraded, "replica 10.0.0.3:6380 lags 3s (max 2s)".to_string())
+            );
+            let waiting = MASTER.replace("state=online,offset=4242", "state=wait_bgsave");
+            let info = ReplicationInfo::parse(&waiting);
+            assert_eq!(
+                info.evaluate(10),
+                (CheckStatus::Degraded, "replica 10.0.0.2:6379 is not online".to_string())
+            );
+        }
+
+        #[test]
+        fn checks_the_link_of_a_replica() {
+            let info = ReplicationInfo::parse(REPLICA);
+            assert_eq!(info.master_link_up, Some(true));
+            assert_eq!(info.evaluate(10).0, CheckStatus::Healthy);
+
+            let down = ReplicationInfo::parse(&REPLICA.replace("status:up", "status:down"));
+            assert_eq!(
+                down.evaluate(10),
+                (CheckStatus::Degraded, "replica link to master is down".to_string())
+            );
+            let stale = ReplicationInfo::parse(&REPLICA.replace("ago:1", "ago:30"));
+            assert_eq!(
+                stale.evaluate(10),
+                (CheckStatus::Degraded, "no data from master for 30s (max 10s)".to_string())
+            );
+        }
+
+        #[test]
+        fn ignores_unrelated_keys() {
+            let info = ReplicationInfo::parse("slave_read_only:1\nslaveX:ip=1\nnot a pair\n");
+            assert!(info.replicas.is_empty());
+            assert_eq!(info.role, "");
+        }
+    }
+}
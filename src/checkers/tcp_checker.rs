@@ -0,0 +1,217 @@
+#[cfg(feature = "ssr")]
+pub mod tcp {
+    use crate::checkers::HealthChecker;
+    use crate::types::{GlobalSettings, HealthCheckStatus, ServiceSettings};
+    use anyhow::{bail, Result};
+    use async_trait::async_trait;
+    use log::{error, info};
+    use regex::Regex;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    const MAX_BANNER_BYTES: usize = 4096;
+    /// How long to wait for the rest of a banner once part of it has arrived.
+    const BANNER_IDLE: Duration = Duration::from_millis(500);
+
+    pub struct TcpChecker {
+        name: String,
+        target: String,
+        payload: Option<String>,
+        expect_prefix: Option<String>,
+        expect_regex: Option<Regex>,
+    }
+
+    impl TcpChecker {
+        pub fn new(
+            name: String,
+            target: String,
+            payload: Option<String>,
+            expect_prefix: Option<String>,
+            expect_regex: Option<Regex>,
+        ) -> Self {
+            TcpChecker {
+                name,
+                target,
+                payload,
+                expect_prefix,
+                expect_regex,
+            }
+        }
+
+        pub fn from_settings(
+            service: &ServiceSettings,
+            _global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            let expect_regex = service
+                .expect_banner_regex
+                .as_deref()
+                .map(Regex::new)
+                .transpose()?;
+            Ok(Arc::new(TcpChecker::new(
+                service.name.clone(),
+                service.url.clone(),
+                service.send_payload.clone(),
+                service.expect_banner.clone(),
+                expect_regex,
+            )))
+        }
+
+        fn expects_banner(&self) -> bool {
+            self.expect_prefix.is_some() || self.expect_regex.is_some()
+        }
+
+        /// Connects, optionally exchanges a payload, and returns the connect time in ms.
+        async fn probe(&self) -> Result<u128> {
+            let start = Instant::now();
+            let mut stream = TcpStream::connect(&self.target).await?;
+            let connect_time = start.elapsed().as_millis();
+
+            if let Some(payload) = &self.payload {
+                stream.write_all(payload.as_bytes()).await?;
+            }
+            if self.expects_banner() {
+                self.read_banner(&mut stream).await?;
+            }
+            Ok(connect_time)
+        }
+
+        /// Reads the first line of the reply and verifies it, stopping early once the part read
+        /// so far decides the prefix check. Servers that keep the socket open without sending a
+        /// newline are judged on what arrived before they went quiet for `BANNER_IDLE`.
+        async fn read_banner(&self, stream: &mut TcpStream) -> Result<()> {
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 512];
+            loop {
+                let read = if buffer.is_empty() {
+                    stream.read(&mut chunk).await?
+                } else {
+                    match tokio::time::timeout(BANNER_IDLE, stream.read(&mut chunk)).await {
+                        Ok(read) => read?,
+                        Err(_) => 0,
+                    }
+                };
+                buffer.extend_from_slice(&chunk[..read]);
+                let text = String::from_utf8_lossy(&buffer);
+                let banner = text.lines().next().unwrap_or_default();
+                let complete = read == 0 || text.contains('\n') || buffer.len() >= MAX_BANNER_BYTES;
+                if complete || self.is_decided(banner) {
+                    return self.verify_banner(banner);
+                }
+            }
+        }
+
+        /// Whether the start of a banner already settles the outcome: it fails the prefix, or
+        /// passes it with no regex left to check. A regex is only matched against a whole line,
+        /// since anchors and later characters can change a partial match.
+        fn is_decided(&self, partial: &str) -> bool {
+            let Some(prefix) = &self.expect_prefix else {
+                return false;
+            };
+            if partial.starts_with(prefix.as_str()) {
+                self.expect_regex.is_none()
+            } else {
+                !prefix.starts_with(partial)
+            }
+        }
+
+        fn verify_banner(&self, banner: &str) -> Result<()> {
+            if let Some(prefix) = &self.expect_prefix {
+                if !banner.starts_with(prefix.as_str()) {
+                    bail!("Banner '{}' does not start with '{}'", banner, prefix);
+                }
+            }
+            if let Some(pattern) = &self.expect_regex {
+                if !pattern.is_match(banner) {
+                    bail!("Banner '{}' does not match /{}/", banner, pattern);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl HealthChecker for TcpChecker {
+        async fn check(&self) -> HealthCheckStatus {
+            let start = Instant::now();
+            match self.probe().await {
+                Ok(connect_time) => {
+                    info!("checked successful. name: {}, connect time: {}ms", self.name, connect_time);
+                    HealthCheckStatus::healthy(
+                        format!("Connected in {}ms", connect_time),
+                        connect_time,
+                    )
+                }
+                Err(e) => {
+                    error!("TCP probe failed for '{}': {}", self.name, e);
+                    HealthCheckStatus::unhealthy(e.to_string(), start.elapsed().as_millis())
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use tokio::net::TcpListener;
+
+        fn checker(target: &str, prefix: Option<&str>, regex: Option<&str>) -> TcpChecker {
+            TcpChecker::new(
+                "banner".to_string(),
+                target.to_string(),
+                None,
+                prefix.map(str::to_string),
+                regex.map(|pattern| Regex::new(pattern).unwrap()),
+            )
+        }
+
+        #[test]
+        fn decides_early_only_on_the_prefix() {
+            let prefix_only = checker("", Some("SSH-2.0-"), None);
+            assert!(!prefix_only.is_decided("SSH-"));
+            assert!(prefix_only.is_decided("SSH-2.0-Open"));
+            assert!(prefix_only.is_decided("HTTP/1.1"));
+
+            let both = checker("", Some("SSH-2.0-"), Some("^SSH-2\\.0-OpenSSH_9$"));
+            assert!(!both.is_decided("SSH-2.0-OpenSSH_9"));
+            assert!(both.is_decided("220 smtp"));
+
+            let regex_only = checker("", None, Some("^OK$"));
+            assert!(!regex_only.is_decided("OK"));
+        }
+
+        #[test]
+        fn verifies_prefix_and_regex() {
+            let both = checker("", Some("SSH-2.0-"), Some("OpenSSH_\\d+$"));
+            assert!(both.verify_banner("SSH-2.0-OpenSSH_9").is_ok());
+            let error = both.verify_banner("SSH-2.0-dropbear").unwrap_err().to_string();
+            assert!(error.contains("does not match"));
+            let error = both.verify_banner("220 smtp").unwrap_err().to_string();
+            assert!(error.contains("does not start with 'SSH-2.0-'"));
+        }
+
+        #[actix_rt::test]
+        async fn matches_the_regex_against_the_whole_line() {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let target = listener.local_addr().unwrap().to_string();
+            tokio::spawn(async move {
+                loop {
+                    let (mut socket, _) = listener.accept().await.unwrap();
+                    tokio::spawn(async move {
+                        socket.write_all(b"OK").await.unwrap();
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        socket.write_all(b", degraded\r\n").await.unwrap();
+                    });
+                }
+            });
+
+            let status = checker(&target, None, Some("^OK$")).check().await;
+            assert_eq!(status.status, crate::types::CheckStatus::Unhealthy);
+            assert!(status.status_message.contains("'OK, degraded'"));
+
+            let status = checker(&target, Some("OK"), Some("degraded$")).check().await;
+            assert_eq!(status.status, crate::types::CheckStatus::Healthy);
+        }
+    }
+}
@@ -0,0 +1,444 @@
+#[cfg(feature = "ssr")]
+pub mod tls {
+    use crate::checkers::HealthChecker;
+    use crate::types::{
+        CertificateThresholds, CheckStatus, GlobalSettings, HealthCheckStatus, ServiceSettings,
+        TlsSettings,
+    };
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use log::{error, info, warn};
+    use reqwest::Url;
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::client::WebPkiServerVerifier;
+    use rustls::crypto::CryptoProvider;
+    use rustls::crypto::{verify_tls12_signature, verify_tls13_signature};
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+    use rustls::{CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+    use std::sync::{Arc, Mutex, OnceLock};
+    use std::time::Instant;
+    use tokio::net::TcpStream;
+    use tokio_rustls::TlsConnector;
+    use x509_parser::extensions::GeneralName;
+    use x509_parser::prelude::{FromDer, X509Certificate};
+
+    /// What was learned about the certificate a server presented.
+    pub struct CertificateReport {
+        /// Expiry of the first certificate in the chain to expire.
+        pub not_after: DateTime<Utc>,
+        pub days_until_expiry: i64,
+        /// Which certificate of the chain `not_after` belongs to.
+        pub expiring_certificate: String,
+        pub issuer: String,
+        pub subject_alt_names: Vec<String>,
+        pub hostname_matches: bool,
+        /// Why the chain failed verification against the trusted roots, if it did.
+        pub validation_error: Option<String>,
+    }
+
+    impl CertificateReport {
+        pub fn evaluate(&self, thresholds: &CertificateThresholds) -> (CheckStatus, String) {
+            let summary = format!(
+                "expires in {} days ({}, {}), issuer: {}, SANs: {}",
+                self.days_until_expiry,
+                self.not_after.format("%Y-%m-%d"),
+                self.expiring_certificate,
+                self.issuer,
+                self.subject_alt_names.join(", ")
+            );
+            if let Some(reason) = &self.validation_error {
+                let status_message = if self.hostname_matches {
+                    format!("Certificate validation failed: {}; {}", reason, summary)
+                } else {
+                    format!("Certificate does not match hostname: {}; {}", reason, summary)
+                };
+                return (CheckStatus::Unhealthy, status_message);
+            }
+            if self.days_until_expiry < thresholds.critical_days {
+                (CheckStatus::Unhealthy, format!("Certificate {}", summary))
+            } else if self.days_until_expiry < thresholds.warning_days {
+                (CheckStatus::Degraded, format!("Certificate {}", summary))
+            } else {
+                (CheckStatus::Healthy, format!("Certificate {}", summary))
+            }
+        }
+    }
+
+    fn crypto_provider() -> Arc<CryptoProvider> {
+        Arc::new(rustls::crypto::ring::default_provider())
+    }
+
+    /// The platform's trusted roots, loaded once.
+    fn native_roots() -> Arc<RootCertStore> {
+        static ROOTS: OnceLock<Arc<RootCertStore>> = OnceLock::new();
+        ROOTS
+            .get_or_init(|| {
+                let loaded = rustls_native_certs::load_native_certs();
+                for e in &loaded.errors {
+                    warn!("Failed to load a native root certificate: {}", e);
+                }
+                let mut roots = RootCertStore::empty();
+                roots.add_parsable_certificates(loaded.certs);
+                Arc::new(roots)
+            })
+            .clone()
+    }
+
+    /// Verifies the chain like a normal client would but records the verdict instead of
+    /// aborting the handshake, so expired or mismatched certificates can still be inspected.
+    #[derive(Debug)]
+    struct RecordingVerifier {
+        inner: Arc<dyn ServerCertVerifier>,
+        outcome: Mutex<Option<rustls::Error>>,
+    }
+
+    impl ServerCertVerifier for RecordingVerifier {
+        fn verify_server_cert(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            intermediates: &[CertificateDer<'_>],
+            server_name: &ServerName<'_>,
+            ocsp_response: &[u8],
+            now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            let verdict = self.inner.verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                ocsp_response,
+                now,
+            );
+            if let Err(e) = verdict {
+                *self.outcome.lock().unwrap() = Some(e);
+            }
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            self.inner.verify_tls12_signature(message, cert, dss)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            self.inner.verify_tls13_signature(message, cert, dss)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.inner.supported_verify_schemes()
+        }
+    }
+
+    /// Accepts any certificate chain but still checks handshake signatures.
+    #[derive(Debug)]
+    struct SkipVerification {
+        provider: Arc<CryptoProvider>,
+    }
+
+    impl ServerCertVerifier for SkipVerification {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls12_signature(
+                message,
+                cert,
+                dss,
+                &self.provider.signature_verification_algorithms,
+            )
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls13_signature(
+                message,
+                cert,
+                dss,
+                &self.provider.signature_verification_algorithms,
+            )
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.provider
+                .signature_verification_algorithms
+                .supported_schemes()
+        }
+    }
+
+    /// The CAs from `ca_file`, or the system roots when none is configured.
+    fn trusted_roots(settings: &TlsSettings) -> Result<Arc<RootCertStore>> {
+        match &settings.ca_file {
+            Some(ca_file) => {
+                let mut roots = RootCertStore::empty();
+                for certificate in CertificateDer::pem_file_iter(ca_file)? {
+                    roots.add(certificate?)?;
+                }
+                Ok(Arc::new(roots))
+            }
+            None => Ok(native_roots()),
+        }
+    }
+
+    /// Verifies against the name the client connected with, or skips verification entirely.
+    fn server_verifier(
+        settings: &TlsSettings,
+        provider: &Arc<CryptoProvider>,
+    ) -> Result<Arc<dyn ServerCertVerifier>> {
+        if settings.insecure_skip_verify {
+            return Ok(Arc::new(SkipVerification {
+                provider: provider.clone(),
+            }));
+        }
+        Ok(WebPkiServerVerifier::builder_with_provider(trusted_roots(settings)?, provider.clone())
+            .build()?)
+    }
+
+    fn tls_config(
+        settings: &TlsSettings,
+        provider: Arc<CryptoProvider>,
+        verifier: Arc<dyn ServerCertVerifier>,
+    ) -> Result<ClientConfig> {
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier);
+        let config = match (&settings.client_cert, &settings.client_key) {
+            (Some(cert), Some(key)) => {
+                let chain = CertificateDer::pem_file_iter(cert)?.collect::<Result<Vec<_>, _>>()?;
+                builder.with_client_auth_cert(chain, PrivateKeyDer::from_pem_file(key)?)?
+            }
+            _ => builder.with_no_client_auth(),
+        };
+        Ok(config)
+    }
+
+    /// Builds the client configuration for a probe's `tls` settings. A `server_name` is
+    /// applied by the transport, which addresses requests to it.
+    pub fn client_tls_config(settings: &TlsSettings) -> Result<ClientConfig> {
+        let provider = crypto_provider();
+        let verifier = server_verifier(settings, &provider)?;
+        tls_config(settings, provider, verifier)
+    }
+
+    /// Performs a TLS handshake with `host:port` and reports on the presented certificates:
+    /// the earliest expiry across the whole chain, and the issuer and names of the leaf.
+    /// The chain is verified with the roots, client certificate and server name from `settings`.
+    pub async fn inspect_certificate(
+        host: &str,
+        port: u16,
+        settings: &TlsSettings,
+    ) -> Result<CertificateReport> {
+        let provider = crypto_provider();
+        let verifier = Arc::new(RecordingVerifier {
+            inner: server_verifier(settings, &provider)?,
+            outcome: Mutex::new(None),
+        });
+        let config = tls_config(settings, provider, verifier.clone())?;
+
+        let server_name =
+            ServerName::try_from(settings.server_name.as_deref().unwrap_or(host).to_string())?;
+        let tcp = TcpStream::connect((host, port)).await?;
+        let stream = TlsConnector::from(Arc::new(config))
+            .connect(server_name, tcp)
+            .await?;
+        let chain = stream
+            .get_ref()
+            .1
+            .peer_certificates()
+            .unwrap_or_default()
+            .iter()
+            .map(|der| {
+                X509Certificate::from_der(der.as_ref())
+                    .map(|(_, certificate)| certificate)
+                    .map_err(|e| anyhow!("failed to parse server certificate: {}", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (expiring, earliest) = chain
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, certificate)| certificate.validity().not_after.timestamp())
+            .ok_or_else(|| anyhow!("server presented no certificate"))?;
+        let certificate = &chain[0];
+        let expiring_certificate = if expiring == 0 {
+            "leaf certificate".to_string()
+        } else {
+            format!("chain certificate '{}'", earliest.subject())
+        };
+
+        let not_after = DateTime::from_timestamp(earliest.validity().not_after.timestamp(), 0)
+            .ok_or_else(|| anyhow!("certificate expiry is out of range"))?;
+        let subject_alt_names = certificate
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|extension| {
+                extension
+                    .value
+                    .general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(dns) => Some(dns.to_string()),
+                        GeneralName::IPAddress(ip) => ip_to_string(ip),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let validation_error = verifier.outcome.lock().unwrap().take();
+        let hostname_matches = !matches!(
+            validation_error,
+            Some(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. }
+            ))
+        );
+
+        Ok(CertificateReport {
+            not_after,
+            days_until_expiry: (not_after - Utc::now()).num_days(),
+            expiring_certificate,
+            issuer: certificate.issuer().to_string(),
+            subject_alt_names,
+            hostname_matches,
+            validation_error: validation_error.map(|e| e.to_string()),
+        })
+    }
+
+    fn ip_to_string(bytes: &[u8]) -> Option<String> {
+        match bytes.len() {
+            4 => <[u8; 4]>::try_from(bytes).ok().map(|ip| std::net::Ipv4Addr::from(ip).to_string()),
+            16 => <[u8; 16]>::try_from(bytes).ok().map(|ip| std::net::Ipv6Addr::from(ip).to_string()),
+            _ => None,
+        }
+    }
+
+    /// Splits a `host:port` or `https://host[:port]` target, defaulting to port 443.
+    pub fn parse_target(target: &str) -> Result<(String, u16)> {
+        if target.contains("://") {
+            let url = Url::parse(target)?;
+            let host = url
+                .host_str()
+                .ok_or_else(|| anyhow!("'{}' has no host", target))?;
+            return Ok((host.to_string(), url.port_or_known_default().unwrap_or(443)));
+        }
+        match target.rsplit_once(':') {
+            Some((host, port)) => Ok((host.to_string(), port.parse()?)),
+            None => Ok((target.to_string(), 443)),
+        }
+    }
+
+    pub struct TlsChecker {
+        name: String,
+        host: String,
+        port: u16,
+        thresholds: CertificateThresholds,
+    }
+
+    impl TlsChecker {
+        pub fn new(name: String, host: String, port: u16, thresholds: CertificateThresholds) -> Self {
+            TlsChecker {
+                name,
+                host,
+                port,
+                thresholds,
+            }
+        }
+
+        pub fn from_settings(
+            service: &ServiceSettings,
+            _global: &GlobalSettings,
+        ) -> Result<Arc<dyn HealthChecker>> {
+            let (host, port) = parse_target(&service.url)?;
+            Ok(Arc::new(TlsChecker::new(
+                service.name.clone(),
+                host,
+                port,
+                service.certificate_thresholds(),
+            )))
+        }
+    }
+
+    #[async_trait]
+    impl HealthChecker for TlsChecker {
+        async fn check(&self) -> HealthCheckStatus {
+            let start = Instant::now();
+            match inspect_certificate(&self.host, self.port, &TlsSettings::default()).await {
+                Ok(report) => {
+                    let (status, status_message) = report.evaluate(&self.thresholds);
+                    info!(
+                        "checked certificate. name: {}, days until expiry: {}",
+                        self.name, report.days_until_expiry
+                    );
+                    HealthCheckStatus::new(status, status_message, start.elapsed().as_millis())
+                }
+                Err(e) => {
+                    error!("TLS handshake failed for '{}': {}", self.name, e);
+                    HealthCheckStatus::unhealthy(
+                        format!("TLS handshake failed: {}", e),
+                        start.elapsed().as_millis(),
+                    )
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn report(days_until_expiry: i64, validation_error: Option<&str>) -> CertificateReport {
+            CertificateReport {
+                not_after: Utc::now() + chrono::Duration::days(days_until_expiry),
+                days_until_expiry,
+                expiring_certificate: "chain certificate 'CN=Intermediate'".to_string(),
+                issuer: "CN=Intermediate".to_string(),
+                subject_alt_names: vec!["example.com".to_string()],
+                hostname_matches: true,
+                validation_error: validation_error.map(str::to_string),
+            }
+        }
+
+        #[test]
+        fn evaluates_expiry_against_thresholds() {
+            let thresholds = CertificateThresholds {
+                warning_days: 30,
+                critical_days: 7,
+            };
+            let (status, message) = report(90, None).evaluate(&thresholds);
+            assert_eq!(status, CheckStatus::Healthy);
+            assert!(message.contains("chain certificate 'CN=Intermediate'"));
+            assert_eq!(report(10, None).evaluate(&thresholds).0, CheckStatus::Degraded);
+            assert_eq!(report(3, None).evaluate(&thresholds).0, CheckStatus::Unhealthy);
+
+            let (status, message) = report(90, Some("expired")).evaluate(&thresholds);
+            assert_eq!(status, CheckStatus::Unhealthy);
+            assert!(message.starts_with("Certificate validation failed: expired"));
+        }
+    }
+}
@@ -3,6 +3,12 @@ pub mod actors;
 pub mod app;
 #[cfg(feature = "ssr")]
 pub mod api;
+#[cfg(feature = "ssr")]
+pub mod checkers;
+#[cfg(feature = "ssr")]
+pub mod metrics;
+#[cfg(feature = "ssr")]
+pub mod storage;
 pub mod types;
 
 #[cfg(feature = "hydrate")]
@@ -4,8 +4,11 @@ async fn main() -> std::io::Result<()> {
     use actix_files::Files;
     use actix_web::*;
     use health_check::actors::BroadcastActor;
-    use health_check::actors::HttpHealthCheckActor;
+    use health_check::actors::{AlertActor, HealthCheckActor, MetricsActor, StorageActor};
     use health_check::app::*;
+    use health_check::checkers::CheckerRegistry;
+    use health_check::metrics::HealthMetrics;
+    use health_check::storage::HistoryStore;
     use health_check::types::ServiceConfiguration;
     use leptos::config::get_configuration;
     use leptos::prelude::*;
@@ -15,7 +18,9 @@ async fn main() -> std::io::Result<()> {
     use std::io::Error;
     use tokio::sync::broadcast;
     use health_check::actors::HealthCheckInfo;
-    use health_check::api::server_api::sse_handler;
+    use health_check::api::server_api::{
+        history_handler, metrics_handler, services_handler, sse_handler, uptime_handler,
+    };
 
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
 
@@ -28,15 +33,27 @@ async fn main() -> std::io::Result<()> {
     let config = ServiceConfiguration::load_from_file("config.toml")
         .map_err(|e| Error::other(format!("{:?}", e)))?;
 
+    let store = HistoryStore::open(&config.storage.path)
+        .await
+        .map_err(|e| Error::other(format!("{:?}", e)))?;
+    actix::Actor::start(StorageActor::new(store.clone(), sender.subscribe()));
+
+    let metrics = HealthMetrics::new().map_err(|e| Error::other(format!("{:?}", e)))?;
+    actix::Actor::start(MetricsActor::new(metrics.clone(), sender.subscribe()));
+
+    let alert_actor = AlertActor::new(config.alerting.clone(), sender.subscribe())
+        .map_err(|e| Error::other(format!("{:?}", e)))?;
+    actix::Actor::start(alert_actor);
+
+    let registry = CheckerRegistry::default();
     for service in config.services {
-        actix::Actor::start(HttpHealthCheckActor::new(
-            service.name,
-            service.url,
-            config.global.check_interval_seconds,
-            service.timeout_seconds
-                .map_or(config.global.timeout_seconds, |v| v),
-            service.response_code.map_or(200, |v| v),
-            service.headers,
+        let checker = registry
+            .build(&service, &config.global)
+            .map_err(|e| Error::other(format!("{:?}", e)))?;
+        actix::Actor::start(HealthCheckActor::new(
+            &service,
+            &config.global,
+            checker,
             broadcast_actor.clone(),
         ));
     }
@@ -50,6 +67,9 @@ async fn main() -> std::io::Result<()> {
 
         App::new()
             .app_data(web::Data::new(sender.clone()))
+            .app_data(web::Data::from(broadcast_actor.clone()))
+            .app_data(web::Data::new(store.clone()))
+            .app_data(web::Data::new(metrics.clone()))
             // serve JS/WASM/CSS from `pkg`
             .service(Files::new("/pkg", format!("{site_root}/pkg")))
             // serve other assets from the `assets` directory
@@ -57,6 +77,10 @@ async fn main() -> std::io::Result<()> {
             // serve the favicon from /favicon.ico
             .service(favicon)
             .service(sse_handler)
+            .service(services_handler)
+            .service(history_handler)
+            .service(uptime_handler)
+            .service(metrics_handler)
             .leptos_routes(routes, {
                 let leptos_options = leptos_options.clone();
                 move || {
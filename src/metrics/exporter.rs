@@ -0,0 +1,99 @@
+#[cfg(feature = "ssr")]
+pub mod prometheus_metrics {
+    use crate::actors::HealthCheckInfo;
+    use crate::types::CheckStatus;
+    use anyhow::Result;
+    use prometheus::{
+        Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry,
+        TextEncoder,
+    };
+
+    const RESPONSE_TIME_BUCKETS: &[f64] = &[
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+    ];
+
+    /// Prometheus metrics derived from the published check results.
+    #[derive(Clone)]
+    pub struct HealthMetrics {
+        registry: Registry,
+        up: GaugeVec,
+        response_time: HistogramVec,
+        last_check: GaugeVec,
+        checks: IntCounterVec,
+    }
+
+    impl HealthMetrics {
+        pub fn new() -> Result<Self> {
+            let registry = Registry::new();
+            let up = GaugeVec::new(
+                Opts::new(
+                    "health_check_up",
+                    "Whether the last check succeeded (1), including degraded results, or failed (0)",
+                ),
+                &["service", "type", "url"],
+            )?;
+            let response_time = HistogramVec::new(
+                HistogramOpts::new(
+                    "health_check_response_time_seconds",
+                    "Response time of health checks",
+                )
+                .buckets(RESPONSE_TIME_BUCKETS.to_vec()),
+                &["service", "type"],
+            )?;
+            let last_check = GaugeVec::new(
+                Opts::new(
+                    "health_check_last_check_timestamp_seconds",
+                    "Unix time of the last completed check",
+                ),
+                &["service", "type"],
+            )?;
+            let checks = IntCounterVec::new(
+                Opts::new("health_check_checks_total", "Completed checks by resulting status"),
+                &["service", "type", "status"],
+            )?;
+
+            registry.register(Box::new(up.clone()))?;
+            registry.register(Box::new(response_time.clone()))?;
+            registry.register(Box::new(last_check.clone()))?;
+            registry.register(Box::new(checks.clone()))?;
+
+            Ok(HealthMetrics {
+                registry,
+                up,
+                response_time,
+                last_check,
+                checks,
+            })
+        }
+
+        pub fn observe(&self, info: &HealthCheckInfo) {
+            let service_type = info.service_type.to_string();
+            let status = &info.latest_status;
+            let is_up = !matches!(status.status, CheckStatus::Unhealthy);
+
+            self.up
+                .with_label_values(&[info.name.as_str(), service_type.as_str(), info.url.as_str()])
+                .set(if is_up { 1.0 } else { 0.0 });
+            self.response_time
+                .with_label_values(&[info.name.as_str(), service_type.as_str()])
+                .observe(status.response_time as f64 / 1000.0);
+            self.last_check
+                .with_label_values(&[info.name.as_str(), service_type.as_str()])
+                .set(status.timestamp.timestamp_millis() as f64 / 1000.0);
+            self.checks
+                .with_label_values(&[
+                    info.name.as_str(),
+                    service_type.as_str(),
+                    status.status.to_string().as_str(),
+                ])
+                .inc();
+        }
+
+        /// Renders all metrics in the Prometheus text exposition format.
+        pub fn render(&self) -> Result<String> {
+            let mut buffer = Vec::new();
+            TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+            Ok(String::from_utf8(buffer)?)
+        }
+    }
+}
@@ -0,0 +1,5 @@
+#[cfg(feature = "ssr")]
+mod exporter;
+
+#[cfg(feature = "ssr")]
+pub use exporter::prometheus_metrics::*;
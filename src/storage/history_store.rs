@@ -0,0 +1,295 @@
+#[cfg(feature = "ssr")]
+pub mod history {
+    use crate::actors::HealthCheckInfo;
+    use crate::types::{CheckStatus, HealthCheckStatus};
+    use anyhow::{anyhow, Result};
+    use chrono::{DateTime, Utc};
+    use serde::Serialize;
+    use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqliteRow};
+    use sqlx::{QueryBuilder, Row, Sqlite};
+    use std::str::FromStr;
+
+    /// Schema changes, applied in order and tracked through `PRAGMA user_version`.
+    const MIGRATIONS: &[&str] = &[
+        "CREATE TABLE check_results (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            service_type TEXT NOT NULL,
+            status TEXT NOT NULL,
+            status_message TEXT NOT NULL,
+            response_time INTEGER NOT NULL,
+            timestamp INTEGER NOT NULL
+        );
+        CREATE INDEX check_results_name_timestamp ON check_results (name, timestamp);",
+        "ALTER TABLE check_results ADD COLUMN attempts INTEGER NOT NULL DEFAULT 1;",
+        "ALTER TABLE check_results ADD COLUMN timings TEXT;",
+        "ALTER TABLE check_results ADD COLUMN effective_status TEXT;",
+    ];
+
+    #[derive(Serialize, Default)]
+    pub struct StatusCounts {
+        pub healthy: u64,
+        pub degraded: u64,
+        pub unhealthy: u64,
+    }
+
+    /// Availability summary of a service over a time range.
+    ///
+    /// Degraded checks count towards uptime: the service answered, just slowly.
+    #[derive(Serialize)]
+    pub struct UptimeReport {
+        pub name: String,
+        pub from: DateTime<Utc>,
+        pub to: DateTime<Utc>,
+        pub total_checks: u64,
+        pub uptime_percentage: Option<f64>,
+        pub average_response_time: Option<f64>,
+        pub p95_response_time: Option<u128>,
+        pub p99_response_time: Option<u128>,
+        pub status_counts: StatusCounts,
+    }
+
+    impl UptimeReport {
+        pub fn from_history(
+            name: &str,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+            history: &[HealthCheckStatus],
+        ) -> Self {
+            let mut status_counts = StatusCounts::default();
+            for check in history {
+                match check.status {
+                    CheckStatus::Healthy => status_counts.healthy += 1,
+                    CheckStatus::Degraded => status_counts.degraded += 1,
+                    CheckStatus::Unhealthy => status_counts.unhealthy += 1,
+                }
+            }
+
+            let total_checks = history.len() as u64;
+            let mut response_times: Vec<u128> =
+                history.iter().map(|check| check.response_time).collect();
+            response_times.sort_unstable();
+            let (uptime_percentage, average_response_time) = if total_checks == 0 {
+                (None, None)
+            } else {
+                let up = status_counts.healthy + status_counts.degraded;
+                let total_time: u128 = response_times.iter().sum();
+                (
+                    Some(up as f64 * 100.0 / total_checks as f64),
+                    Some(total_time as f64 / total_checks as f64),
+                )
+            };
+
+            UptimeReport {
+                name: name.to_string(),
+                from,
+                to,
+                total_checks,
+                uptime_percentage,
+                average_response_time,
+                p95_response_time: percentile(&response_times, 95),
+                p99_response_time: percentile(&response_times, 99),
+                status_counts,
+            }
+        }
+    }
+
+    /// Nearest-rank percentile of an ascending slice.
+    fn percentile(sorted: &[u128], percent: usize) -> Option<u128> {
+        if sorted.is_empty() {
+            return None;
+        }
+        let rank = (percent * sorted.len()).div_ceil(100).max(1);
+        sorted.get(rank - 1).copied()
+    }
+
+    /// Check results persisted in an embedded SQLite database.
+    #[derive(Clone)]
+    pub struct HistoryStore {
+        pool: SqlitePool,
+    }
+
+    impl HistoryStore {
+        pub async fn open(path: &str) -> Result<Self> {
+            let options = SqliteConnectOptions::new()
+                .filename(path)
+                .create_if_missing(true)
+                .journal_mode(SqliteJournalMode::Wal);
+            let pool = SqlitePool::connect_with(options).await?;
+            let store = HistoryStore { pool };
+            store.migrate().await?;
+            Ok(store)
+        }
+
+        async fn migrate(&self) -> Result<()> {
+            let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+                .fetch_one(&self.pool)
+                .await?;
+            for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+                let mut tx = self.pool.begin().await?;
+                sqlx::raw_sql(migration).execute(&mut *tx).await?;
+                sqlx::raw_sql(&format!("PRAGMA user_version = {}", index + 1))
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+            }
+            Ok(())
+        }
+
+        /// Stores the raw check result, which history and uptime are computed from, along
+        /// with the damped status the dashboard showed at the time.
+        pub async fn insert(&self, info: &HealthCheckInfo) -> Result<()> {
+            let status = &info.raw_status;
+            sqlx::query(
+                "INSERT INTO check_results
+                    (name, service_type, status, status_message, response_time, timestamp, attempts,
+                     timings, effective_status)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&info.name)
+            .bind(info.service_type.to_string())
+            .bind(status.status.to_string())
+            .bind(&status.status_message)
+            .bind(i64::try_from(status.response_time).unwrap_or(i64::MAX))
+            .bind(status.timestamp.timestamp_millis())
+            .bind(i64::from(status.attempts))
+            .bind(status.timings.map(|t| serde_json::to_string(&t)).transpose()?)
+            .bind(info.latest_status.status.to_string())
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        }
+
+        /// Raw results for `name` within `[from, to]`, oldest first.
+        ///
+        /// With a `limit`, only the most recent `limit` results of the range are returned.
+        pub async fn history(
+            &self,
+            name: &str,
+            from: Option<DateTime<Utc>>,
+            to: Option<DateTime<Utc>>,
+            limit: Option<u32>,
+        ) -> Result<Vec<HealthCheckStatus>> {
+            let mut query = QueryBuilder::<Sqlite>::new(
+                "SELECT status, status_message, response_time, timestamp, attempts, timings
+                 FROM check_results WHERE name = ",
+            );
+            query.push_bind(name);
+            if let Some(from) = from {
+                query.push(" AND timestamp >= ").push_bind(from.timestamp_millis());
+            }
+            if let Some(to) = to {
+                query.push(" AND timestamp <= ").push_bind(to.timestamp_millis());
+            }
+            query.push(" ORDER BY timestamp DESC");
+            if let Some(limit) = limit {
+                query.push(" LIMIT ").push_bind(i64::from(limit));
+            }
+
+            let rows = query.build().fetch_all(&self.pool).await?;
+            let mut history = rows
+                .iter()
+                .map(Self::status_from_row)
+                .collect::<Result<Vec<_>>>()?;
+            history.reverse();
+            Ok(history)
+        }
+
+        pub async fn uptime(
+            &self,
+            name: &str,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<UptimeReport> {
+            let history = self.history(name, Some(from), Some(to), None).await?;
+            Ok(UptimeReport::from_history(name, from, to, &history))
+        }
+
+        fn status_from_row(row: &SqliteRow) -> Result<HealthCheckStatus> {
+            let status: String = row.try_get("status")?;
+            let response_time: i64 = row.try_get("response_time")?;
+            let timestamp: i64 = row.try_get("timestamp")?;
+            let attempts: i64 = row.try_get("attempts")?;
+            let timings: Option<String> = row.try_get("timings")?;
+            Ok(HealthCheckStatus {
+                status: CheckStatus::from_str(&status).map_err(|e| anyhow!(e))?,
+                status_message: row.try_get("status_message")?,
+                response_time: u128::try_from(response_time).unwrap_or_default(),
+                timestamp: DateTime::from_timestamp_millis(timestamp)
+                    .ok_or_else(|| anyhow!("invalid timestamp {} in check history", timestamp))?,
+                attempts: u32::try_from(attempts).unwrap_or(1),
+                timings: timings.as_deref().map(serde_json::from_str).transpose()?,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn check(status: CheckStatus, response_time: u128) -> HealthCheckStatus {
+            HealthCheckStatus::new(status, "", response_time)
+        }
+
+        #[test]
+        fn percentile_uses_nearest_rank() {
+            let sorted: Vec<u128> = (1..=100).collect();
+            assert_eq!(percentile(&sorted, 95), Some(95));
+            assert_eq!(percentile(&sorted, 99), Some(99));
+            assert_eq!(percentile(&sorted, 100), Some(100));
+            assert_eq!(percentile(&[7], 95), Some(7));
+            assert_eq!(percentile(&[1, 2, 3], 0), Some(1));
+            assert_eq!(percentile(&[10, 20, 30, 40], 50), Some(20));
+            assert_eq!(percentile(&[], 95), None);
+        }
+
+        #[test]
+        fn uptime_counts_degraded_checks_as_up() {
+            let history = [
+                check(CheckStatus::Healthy, 10),
+                check(CheckStatus::Degraded, 30),
+                check(CheckStatus::Unhealthy, 20),
+                check(CheckStatus::Healthy, 40),
+            ];
+            let now = Utc::now();
+            let report = UptimeReport::from_history("api", now, now, &history);
+            assert_eq!(report.total_checks, 4);
+            assert_eq!(report.uptime_percentage, Some(75.0));
+            assert_eq!(report.average_response_time, Some(25.0));
+            assert_eq!(report.p95_response_time, Some(40));
+            assert_eq!(report.status_counts.unhealthy, 1);
+
+            let empty = UptimeReport::from_history("api", now, now, &[]);
+            assert_eq!(empty.uptime_percentage, None);
+            assert_eq!(empty.p99_response_time, None);
+        }
+
+        #[actix_rt::test]
+        async fn history_and_uptime_use_raw_results() {
+            let path = std::env::temp_dir().join(format!("history-{}.db", std::process::id()));
+            let store = HistoryStore::open(path.to_str().unwrap()).await.unwrap();
+            let info = HealthCheckInfo {
+                name: "api".to_string(),
+                service_type: crate::types::ServiceType::Http,
+                url: "http://localhost".to_string(),
+                interval_seconds: 30,
+                latest_status: check(CheckStatus::Healthy, 10),
+                raw_status: check(CheckStatus::Unhealthy, 20),
+                flapping: false,
+            };
+            store.insert(&info).await.unwrap();
+
+            let history = store.history("api", None, None, None).await.unwrap();
+            let hour = chrono::Duration::hours(1);
+            let uptime = store.uptime("api", Utc::now() - hour, Utc::now() + hour).await.unwrap();
+            for suffix in ["", "-wal", "-shm"] {
+                let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+            }
+
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].status, CheckStatus::Unhealthy);
+            assert_eq!(history[0].response_time, 20);
+            assert_eq!(uptime.uptime_percentage, Some(0.0));
+        }
+    }
+}
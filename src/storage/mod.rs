@@ -0,0 +1,5 @@
+#[cfg(feature = "ssr")]
+mod history_store;
+
+#[cfg(feature = "ssr")]
+pub use history_store::history::*;
@@ -1,13 +1,22 @@
 #[cfg(feature = "ssr")]
 pub mod configs {
-    use anyhow::Result;
+    use anyhow::{anyhow, bail, Result};
+    use regex::Regex;
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+    use reqwest::{Method, Url};
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
     use serde::{Deserialize, Serialize};
-    use crate::types::ServiceType;
+    use std::fmt::Display;
+    use std::net::{IpAddr, SocketAddr};
+    use crate::types::{CheckStatus, HealthCheckStatus, ServiceType};
 
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct GlobalSettings {
         pub check_interval_seconds: u64,
         pub timeout_seconds: u64,
+        pub degraded_after_ms: Option<u64>,
+        pub unhealthy_after_ms: Option<u64>,
     }
 
     impl Default for GlobalSettings {
@@ -15,6 +24,392 @@ pub mod configs {
             Self {
                 check_interval_seconds: 60,
                 timeout_seconds: 5,
+                degraded_after_ms: None,
+                unhealthy_after_ms: None,
+            }
+        }
+    }
+
+    /// Response time limits above which an otherwise successful check is downgraded.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct LatencyThresholds {
+        pub degraded_after_ms: Option<u64>,
+        pub unhealthy_after_ms: Option<u64>,
+    }
+
+    impl LatencyThresholds {
+        pub fn apply(&self, mut status: HealthCheckStatus) -> HealthCheckStatus {
+            if matches!(status.status, CheckStatus::Unhealthy) {
+                return status;
+            }
+            let elapsed = status.response_time;
+            if let Some(limit) = self.unhealthy_after_ms.filter(|limit| elapsed > u128::from(*limit)) {
+                status.status = CheckStatus::Unhealthy;
+                status.status_message = format!(
+                    "Response took {}ms, exceeding the unhealthy threshold of {}ms",
+                    elapsed, limit
+                );
+            } else if let Some(limit) = self.degraded_after_ms.filter(|limit| elapsed > u128::from(*limit)) {
+                status.status = CheckStatus::Degraded;
+                status.status_message = format!(
+                    "Slow response: took {}ms, exceeding the degraded threshold of {}ms",
+                    elapsed, limit
+                );
+            }
+            status
+        }
+    }
+
+    /// How many consecutive results it takes to change a service's reported status,
+    /// and how many status changes within a window mark it as flapping.
+    #[derive(Clone, Copy, Debug)]
+    pub struct DampingSettings {
+        pub failures_before_unhealthy: u32,
+        pub successes_before_healthy: u32,
+        pub flap_threshold: Option<u32>,
+        pub flap_window_seconds: u64,
+    }
+
+    /// Immediate retries of a failed check before its failure is recorded.
+    #[derive(Clone, Copy, Debug)]
+    pub struct RetrySettings {
+        pub retries: u32,
+        /// Delay before the first retry; doubled for every further attempt.
+        pub retry_delay_ms: u64,
+    }
+
+    /// Days before certificate expiry at which a service turns degraded, then unhealthy.
+    #[derive(Clone, Copy, Debug)]
+    pub struct CertificateThresholds {
+        pub warning_days: i64,
+        pub critical_days: i64,
+    }
+
+    /// URL schemes a `db` service may use; the scheme selects the driver.
+    pub const DB_URL_SCHEMES: &[&str] = &["postgres", "postgresql", "mysql", "mariadb", "sqlite"];
+
+    #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+    #[serde(rename_all = "UPPERCASE")]
+    pub enum DnsRecordType {
+        #[default]
+        A,
+        Aaaa,
+        Cname,
+        Mx,
+        Txt,
+        Srv,
+    }
+
+    impl Display for DnsRecordType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                DnsRecordType::A => write!(f, "A"),
+                DnsRecordType::Aaaa => write!(f, "AAAA"),
+                DnsRecordType::Cname => write!(f, "CNAME"),
+                DnsRecordType::Mx => write!(f, "MX"),
+                DnsRecordType::Txt => write!(f, "TXT"),
+                DnsRecordType::Srv => write!(f, "SRV"),
+            }
+        }
+    }
+
+    /// Scalar a database probe query must return, e.g. `1`, `0.5`, `true` or `"ok"`.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(untagged)]
+    pub enum ExpectedResult {
+        Bool(bool),
+        Integer(i64),
+        Float(f64),
+        Text(String),
+    }
+
+    impl Display for ExpectedResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ExpectedResult::Bool(value) => write!(f, "{}", value),
+                ExpectedResult::Integer(value) => write!(f, "{}", value),
+                ExpectedResult::Float(value) => write!(f, "{}", value),
+                ExpectedResult::Text(value) => write!(f, "'{}'", value),
+            }
+        }
+    }
+
+    /// What an HTTP check sends.
+    #[derive(Clone, Debug)]
+    pub struct HttpRequestSettings {
+        pub method: Method,
+        pub headers: HeaderMap,
+        pub body: Option<Vec<u8>>,
+        pub auth: Option<AuthSettings>,
+        pub redirects: RedirectSettings,
+    }
+
+    pub const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+    /// `follow_redirects = true` (up to 10 hops), `false`, or a maximum number of hops.
+    #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+    #[serde(untagged)]
+    pub enum FollowRedirects {
+        Enabled(bool),
+        MaxHops(usize),
+    }
+
+    impl FollowRedirects {
+        pub fn max_hops(&self) -> usize {
+            match self {
+                FollowRedirects::Enabled(true) => DEFAULT_MAX_REDIRECTS,
+                FollowRedirects::Enabled(false) => 0,
+                FollowRedirects::MaxHops(hops) => *hops,
+            }
+        }
+    }
+
+    /// How an HTTP check follows redirects and where it expects them to lead.
+    #[derive(Clone, Debug)]
+    pub struct RedirectSettings {
+        pub max_hops: usize,
+        /// URL the redirect chain must end at.
+        pub expect_final_url: Option<Url>,
+        /// Target of the first redirect, resolved against the service URL.
+        pub expect_location: Option<Url>,
+    }
+
+    /// TLS options for probes that talk to private or mutually authenticated endpoints.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub struct TlsSettings {
+        /// PEM bundle of CAs trusted instead of the system roots.
+        pub ca_file: Option<String>,
+        /// PEM client certificate chain presented to the server; requires `client_key`.
+        pub client_cert: Option<String>,
+        pub client_key: Option<String>,
+        /// Name sent as SNI and Host header, and verified against the server certificate, when
+        /// it differs from the URL host. Connections still go to the host from the URL.
+        pub server_name: Option<String>,
+        /// Accepts any server certificate. Only for endpoints that cannot be verified otherwise.
+        #[serde(default)]
+        pub insecure_skip_verify: bool,
+    }
+
+    impl TlsSettings {
+        pub fn validate(&self) -> Result<()> {
+            if let Some(ca_file) = &self.ca_file {
+                let certificates = CertificateDer::pem_file_iter(ca_file)
+                    .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+                    .map_err(|e| anyhow!("cannot read ca_file '{}': {}", ca_file, e))?;
+                if certificates.is_empty() {
+                    bail!("ca_file '{}' contains no certificates", ca_file);
+                }
+            }
+            match (&self.client_cert, &self.client_key) {
+                (Some(cert), Some(key)) => {
+                    CertificateDer::pem_file_iter(cert)
+                        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+                        .map_err(|e| anyhow!("cannot read client_cert '{}': {}", cert, e))?;
+                    PrivateKeyDer::from_pem_file(key)
+                        .map_err(|e| anyhow!("cannot read client_key '{}': {}", key, e))?;
+                }
+                (None, None) => {}
+                _ => bail!("client_cert and client_key must be set together"),
+            }
+            if let Some(server_name) = &self.server_name {
+                ServerName::try_from(server_name.as_str())
+                    .map_err(|e| anyhow!("invalid server_name '{}': {}", server_name, e))?;
+            }
+            if self.insecure_skip_verify && (self.ca_file.is_some() || self.server_name.is_some()) {
+                bail!("insecure_skip_verify cannot be combined with ca_file or server_name");
+            }
+            Ok(())
+        }
+    }
+
+    /// A credential written inline or as `{ env = "VAR" }` to read it from the environment.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(untagged)]
+    pub enum Secret {
+        Env { env: String },
+        Value(String),
+    }
+
+    impl Secret {
+        pub fn resolve(&self) -> Result<String> {
+            match self {
+                Secret::Env { env } => std::env::var(env)
+                    .map_err(|_| anyhow!("environment variable '{}' is not set", env)),
+                Secret::Value(value) => Ok(value.clone()),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    pub enum AuthSettings {
+        Basic {
+            username: String,
+            password: Secret,
+        },
+        Bearer {
+            token: Secret,
+        },
+        /// Client-credentials grant; the token is cached and refreshed before it expires.
+        Oauth2 {
+            token_url: String,
+            client_id: String,
+            client_secret: Secret,
+            #[serde(default)]
+            scopes: Vec<String>,
+        },
+    }
+
+    impl AuthSettings {
+        pub fn validate(&self) -> Result<()> {
+            match self {
+                AuthSettings::Basic { password, .. } => {
+                    password.resolve()?;
+                }
+                AuthSettings::Bearer { token } => {
+                    token.resolve()?;
+                }
+                AuthSettings::Oauth2 {
+                    token_url,
+                    client_secret,
+                    ..
+                } => {
+                    reqwest::Url::parse(token_url)
+                        .map_err(|e| anyhow!("invalid token_url '{}': {}", token_url, e))?;
+                    client_secret.resolve()?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// A check on the HTTP response body; failing it sets `on_failure` (unhealthy by default).
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct BodyAssertion {
+        #[serde(flatten)]
+        pub rule: BodyRule,
+        #[serde(default = "default_assertion_failure")]
+        pub on_failure: CheckStatus,
+    }
+
+    fn default_assertion_failure() -> CheckStatus {
+        CheckStatus::Unhealthy
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(tag = "type", rename_all = "snake_case")]
+    pub enum BodyRule {
+        Contains { value: String },
+        NotContains { value: String },
+        Regex { pattern: String },
+        /// Without `equals` the path only has to match something.
+        JsonPath {
+            path: String,
+            equals: Option<serde_json::Value>,
+        },
+        MaxSize { bytes: usize },
+    }
+
+    impl BodyAssertion {
+        /// The smallest `max_size` among `assertions`, if any sets one.
+        pub fn size_limit(assertions: &[BodyAssertion]) -> Option<usize> {
+            assertions
+                .iter()
+                .filter_map(|assertion| match assertion.rule {
+                    BodyRule::MaxSize { bytes } => Some(bytes),
+                    _ => None,
+                })
+                .min()
+        }
+
+        pub fn validate(&self) -> Result<()> {
+            if self.on_failure == CheckStatus::Healthy {
+                bail!("assertion on_failure must be Degraded or Unhealthy");
+            }
+            match &self.rule {
+                BodyRule::Regex { pattern } => {
+                    Regex::new(pattern).map_err(|e| anyhow!("invalid assertion regex: {}", e))?;
+                }
+                BodyRule::JsonPath { path, .. } => {
+                    serde_json_path::JsonPath::parse(path)
+                        .map_err(|e| anyhow!("invalid JSONPath '{}': {}", path, e))?;
+                }
+                _ => {}
+            }
+            Ok(())
+        }
+    }
+
+    /// Accepted HTTP status codes, written as `200`, `"200-299"` or a list of either.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(untagged)]
+    pub enum ExpectedStatus {
+        Code(u16),
+        Range(String),
+        List(Vec<ExpectedStatus>),
+    }
+
+    impl ExpectedStatus {
+        pub fn matches(&self, code: u16) -> bool {
+            match self {
+                ExpectedStatus::Code(expected) => *expected == code,
+                ExpectedStatus::Range(range) => Self::parse_range(range)
+                    .map(|(low, high)| (low..=high).contains(&code))
+                    .unwrap_or(false),
+                ExpectedStatus::List(items) => items.iter().any(|item| item.matches(code)),
+            }
+        }
+
+        pub fn validate(&self) -> Result<()> {
+            match self {
+                ExpectedStatus::Code(code) => {
+                    if !(100..=999).contains(code) {
+                        bail!("invalid status code {}", code);
+                    }
+                }
+                ExpectedStatus::Range(range) => {
+                    Self::parse_range(range)?;
+                }
+                ExpectedStatus::List(items) => {
+                    if items.is_empty() {
+                        bail!("expected status list is empty");
+                    }
+                    for item in items {
+                        item.validate()?;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        fn parse_range(range: &str) -> Result<(u16, u16)> {
+            let (low, high) = range
+                .split_once('-')
+                .ok_or_else(|| anyhow!("invalid status range '{}', expected \"low-high\"", range))?;
+            let low: u16 = low
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("invalid status range '{}'", range))?;
+            let high: u16 = high
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("invalid status range '{}'", range))?;
+            if low > high {
+                bail!("invalid status range '{}', lower bound exceeds upper bound", range);
+            }
+            Ok((low, high))
+        }
+    }
+
+    impl Display for ExpectedStatus {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ExpectedStatus::Code(code) => write!(f, "{}", code),
+                ExpectedStatus::Range(range) => write!(f, "{}", range),
+                ExpectedStatus::List(items) => {
+                    let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                    write!(f, "{}", items.join(", "))
+                }
             }
         }
     }
@@ -25,16 +420,419 @@ pub mod configs {
         pub url: String,
         pub check_interval_seconds: Option<u16>,
         pub timeout_seconds: Option<u64>,
-        pub response_code: Option<u16>,
+        pub response_code: Option<ExpectedStatus>,
+        pub degraded_after_ms: Option<u64>,
+        pub unhealthy_after_ms: Option<u64>,
         #[serde(rename = "type")]
         pub service_type: ServiceType,
+        #[serde(default)]
         pub headers: Vec<String>,
+        /// HTTP checks: request method, `GET` by default.
+        pub method: Option<String>,
+        /// HTTP checks: request body sent as-is.
+        pub body: Option<String>,
+        /// HTTP checks: file whose contents are sent as the request body.
+        pub body_file: Option<String>,
+        /// HTTP checks: shorthand for a `Content-Type` header.
+        pub content_type: Option<String>,
+        /// HTTP checks: credentials added to every request.
+        pub auth: Option<AuthSettings>,
+        /// HTTP checks: whether and how far to follow redirects; 10 hops by default.
+        pub follow_redirects: Option<FollowRedirects>,
+        /// HTTP checks: URL the redirect chain must end at.
+        pub expect_final_url: Option<String>,
+        /// HTTP checks: expected target of the first redirect, absolute or relative to `url`.
+        pub expect_location: Option<String>,
+        /// HTTP checks: CA bundle, client certificate and verification options.
+        pub tls: Option<TlsSettings>,
+        /// HTTP checks: open a new connection for every check instead of reusing pooled ones,
+        /// so `response_time` includes connection setup.
+        #[serde(default)]
+        pub fresh_connection: bool,
+        /// HTTP checks: measure DNS, connect, TLS, first byte and transfer times, summed over
+        /// redirects. Requests then go over HTTP/1.1 without a proxy, and the body is read in
+        /// full or up to a `max_size` assertion. Requires `fresh_connection`.
+        #[serde(default)]
+        pub record_timings: bool,
+        pub query: Option<String>,
+        /// Database checks: value the first column of the first row must equal.
+        pub expect_result: Option<ExpectedResult>,
+        pub failures_before_unhealthy: Option<u32>,
+        pub successes_before_healthy: Option<u32>,
+        pub flap_threshold: Option<u32>,
+        pub flap_window_seconds: Option<u64>,
+        pub retries: Option<u32>,
+        pub retry_delay_ms: Option<u64>,
+        /// TCP checks: payload written once connected, e.g. `"PING\r\n"`.
+        pub send_payload: Option<String>,
+        /// TCP checks: prefix the first line of the reply must start with.
+        pub expect_banner: Option<String>,
+        /// TCP checks: pattern the first line of the reply must match.
+        pub expect_banner_regex: Option<String>,
+        /// HTTP checks: also inspect the certificate presented by `https` URLs.
+        #[serde(default)]
+        pub check_certificate: bool,
+        pub cert_warning_days: Option<i64>,
+        pub cert_critical_days: Option<i64>,
+        /// HTTP checks: assertions on the response body, evaluated in order.
+        #[serde(default)]
+        pub body_assertions: Vec<BodyAssertion>,
+        /// DNS checks: record type to query for the name in `url`.
+        #[serde(default)]
+        pub record_type: DnsRecordType,
+        /// DNS checks: `ip` or `ip:port` of the resolver to ask; the system resolver otherwise.
+        pub resolver: Option<String>,
+        /// DNS checks: answers that must all be present.
+        #[serde(default)]
+        pub expect_answers: Vec<String>,
+        /// DNS checks: minimum number of answers.
+        pub min_answers: Option<usize>,
+        /// Redis checks: also read `INFO replication` and report link or lag problems.
+        #[serde(default)]
+        pub check_replication: bool,
+        /// Redis checks: replication lag in seconds above which the service is degraded.
+        pub max_replication_lag_seconds: Option<u64>,
+        /// gRPC checks: service name sent in the health `Check` request; empty checks the whole server.
+        pub grpc_service: Option<String>,
+    }
+
+    impl ServiceSettings {
+        /// Parses the configured `"Name: value"` header strings.
+        pub fn header_map(&self) -> Result<HeaderMap> {
+            let mut headers = HeaderMap::new();
+            for header in &self.headers {
+                let (name, value) = header.split_once(':').ok_or_else(|| {
+                    anyhow!(
+                        "service '{}': malformed header '{}', expected \"Name: value\"",
+                        self.name,
+                        header
+                    )
+                })?;
+                let name = HeaderName::from_bytes(name.trim().as_bytes()).map_err(|e| {
+                    anyhow!("service '{}': invalid header name in '{}': {}", self.name, header, e)
+                })?;
+                let value = HeaderValue::from_str(value.trim()).map_err(|e| {
+                    anyhow!("service '{}': invalid header value in '{}': {}", self.name, header, e)
+                })?;
+                headers.append(name, value);
+            }
+            Ok(headers)
+        }
+
+        /// Builds the request from `method`, `headers`, `content_type` and `body` or `body_file`.
+        pub fn http_request(&self) -> Result<HttpRequestSettings> {
+            let method = match &self.method {
+                Some(method) => Method::from_bytes(method.to_uppercase().as_bytes()).map_err(|_| {
+                    anyhow!("service '{}': invalid HTTP method '{}'", self.name, method)
+                })?,
+                None => Method::GET,
+            };
+            let mut headers = self.header_map()?;
+            if let Some(content_type) = &self.content_type {
+                let value = HeaderValue::from_str(content_type).map_err(|e| {
+                    anyhow!("service '{}': invalid content_type '{}': {}", self.name, content_type, e)
+                })?;
+                headers.insert(CONTENT_TYPE, value);
+            }
+            let body = match (&self.body, &self.body_file) {
+                (Some(_), Some(_)) => {
+                    bail!("service '{}': set either body or body_file, not both", self.name)
+                }
+                (Some(body), None) => Some(body.clone().into_bytes()),
+                (None, Some(path)) => Some(std::fs::read(path).map_err(|e| {
+                    anyhow!("service '{}': cannot read body_file '{}': {}", self.name, path, e)
+                })?),
+                (None, None) => None,
+            };
+            Ok(HttpRequestSettings {
+                method,
+                headers,
+                body,
+                auth: self.auth.clone(),
+                redirects: self.redirect_settings()?,
+            })
+        }
+
+        pub fn redirect_settings(&self) -> Result<RedirectSettings> {
+            let base = Url::parse(&self.url)
+                .map_err(|e| anyhow!("service '{}': invalid url '{}': {}", self.name, self.url, e))?;
+            let expect_final_url = self
+                .expect_final_url
+                .as_deref()
+                .map(Url::parse)
+                .transpose()
+                .map_err(|e| anyhow!("service '{}': invalid expect_final_url: {}", self.name, e))?;
+            let expect_location = self
+                .expect_location
+                .as_deref()
+                .map(|location| base.join(location))
+                .transpose()
+                .map_err(|e| anyhow!("service '{}': invalid expect_location: {}", self.name, e))?;
+            Ok(RedirectSettings {
+                max_hops: self
+                    .follow_redirects
+                    .map_or(DEFAULT_MAX_REDIRECTS, |follow| follow.max_hops()),
+                expect_final_url,
+                expect_location,
+            })
+        }
+
+        pub fn interval_seconds(&self, global: &GlobalSettings) -> u64 {
+            self.check_interval_seconds
+                .map_or(global.check_interval_seconds, u64::from)
+        }
+
+        pub fn latency_thresholds(&self, global: &GlobalSettings) -> LatencyThresholds {
+            LatencyThresholds {
+                degraded_after_ms: self.degraded_after_ms.or(global.degraded_after_ms),
+                unhealthy_after_ms: self.unhealthy_after_ms.or(global.unhealthy_after_ms),
+            }
+        }
+
+        pub fn damping_settings(&self) -> DampingSettings {
+            DampingSettings {
+                failures_before_unhealthy: self.failures_before_unhealthy.unwrap_or(1),
+                successes_before_healthy: self.successes_before_healthy.unwrap_or(1),
+                flap_threshold: self.flap_threshold,
+                flap_window_seconds: self.flap_window_seconds.unwrap_or(600),
+            }
+        }
+
+        pub fn retry_settings(&self) -> RetrySettings {
+            RetrySettings {
+                retries: self.retries.unwrap_or(0),
+                retry_delay_ms: self.retry_delay_ms.unwrap_or(200),
+            }
+        }
+
+        pub fn certificate_thresholds(&self) -> CertificateThresholds {
+            CertificateThresholds {
+                warning_days: self.cert_warning_days.unwrap_or(14),
+                critical_days: self.cert_critical_days.unwrap_or(7),
+            }
+        }
+
+        /// Parses `resolver` into a socket address, defaulting to port 53.
+        pub fn resolver_addr(&self) -> Result<Option<SocketAddr>> {
+            let Some(resolver) = &self.resolver else {
+                return Ok(None);
+            };
+            if let Ok(addr) = resolver.parse::<SocketAddr>() {
+                return Ok(Some(addr));
+            }
+            let ip = resolver.parse::<IpAddr>().map_err(|_| {
+                anyhow!(
+                    "service '{}': invalid resolver '{}', expected \"ip\" or \"ip:port\"",
+                    self.name,
+                    resolver
+                )
+            })?;
+            Ok(Some(SocketAddr::new(ip, 53)))
+        }
+
+        pub fn validate(&self, global: &GlobalSettings) -> Result<()> {
+            self.resolver_addr()?;
+            self.header_map()?;
+            // Covers the global default too, which applies when the service sets none.
+            if self.interval_seconds(global) == 0 {
+                bail!("service '{}': check_interval_seconds must be at least 1", self.name);
+            }
+            if let Some(auth) = &self.auth {
+                auth.validate()
+                    .map_err(|e| anyhow!("service '{}': auth: {}", self.name, e))?;
+            }
+            if let Some(tls) = &self.tls {
+                if self.service_type != ServiceType::Http {
+                    bail!("service '{}': tls settings only apply to http services", self.name);
+                }
+                tls.validate()
+                    .map_err(|e| anyhow!("service '{}': tls: {}", self.name, e))?;
+            }
+            if self.service_type == ServiceType::Http {
+                let request = self.http_request()?;
+                if request.method == Method::HEAD && !self.body_assertions.is_empty() {
+                    bail!(
+                        "service '{}': body_assertions cannot be used with HEAD requests",
+                        self.name
+                    );
+                }
+                if self.record_timings && !self.fresh_connection {
+                    bail!(
+                        "service '{}': record_timings measures new connections and requires fresh_connection = true",
+                        self.name
+                    );
+                }
+            }
+            let damping = self.damping_settings();
+            if damping.failures_before_unhealthy == 0 || damping.successes_before_healthy == 0 {
+                bail!(
+                    "service '{}': failures_before_unhealthy and successes_before_healthy must be at least 1",
+                    self.name
+                );
+            }
+            if damping.flap_threshold.is_some_and(|threshold| threshold < 2) {
+                bail!("service '{}': flap_threshold must be at least 2", self.name);
+            }
+            let thresholds = self.latency_thresholds(global);
+            if let (Some(degraded), Some(unhealthy)) =
+                (thresholds.degraded_after_ms, thresholds.unhealthy_after_ms)
+            {
+                if degraded >= unhealthy {
+                    bail!(
+                        "service '{}': degraded_after_ms ({}) must be lower than unhealthy_after_ms ({})",
+                        self.name,
+                        degraded,
+                        unhealthy
+                    );
+                }
+            }
+            if self.service_type == ServiceType::Tcp && !self.url.contains(':') {
+                bail!(
+                    "service '{}': tcp target '{}' must be in \"host:port\" form",
+                    self.name,
+                    self.url
+                );
+            }
+            if self.check_certificate && !self.url.starts_with("https://") {
+                bail!(
+                    "service '{}': check_certificate requires an https url",
+                    self.name
+                );
+            }
+            let certificate = self.certificate_thresholds();
+            if certificate.critical_days > certificate.warning_days {
+                bail!(
+                    "service '{}': cert_critical_days ({}) must not exceed cert_warning_days ({})",
+                    self.name,
+                    certificate.critical_days,
+                    certificate.warning_days
+                );
+            }
+            if self.service_type == ServiceType::Db
+                && !DB_URL_SCHEMES
+                    .iter()
+                    .any(|scheme| self.url.starts_with(&format!("{}:", scheme)))
+            {
+                bail!(
+                    "service '{}': unsupported database url '{}', expected one of {}",
+                    self.name,
+                    self.url,
+                    DB_URL_SCHEMES.join(", ")
+                );
+            }
+            if self.service_type == ServiceType::Grpc
+                && !(self.url.starts_with("http://") || self.url.starts_with("https://"))
+            {
+                bail!(
+                    "service '{}': grpc url '{}' must start with \"http://\" or \"https://\"",
+                    self.name,
+                    self.url
+                );
+            }
+            if self.service_type == ServiceType::Redis && !self.url.starts_with("redis://") {
+                bail!(
+                    "service '{}': redis url '{}' must start with \"redis://\"",
+                    self.name,
+                    self.url
+                );
+            }
+            if self.min_answers == Some(0) {
+                bail!("service '{}': min_answers must be at least 1", self.name);
+            }
+            if let Some(pattern) = &self.expect_banner_regex {
+                Regex::new(pattern).map_err(|e| {
+                    anyhow!("service '{}': invalid expect_banner_regex: {}", self.name, e)
+                })?;
+            }
+            for assertion in &self.body_assertions {
+                assertion
+                    .validate()
+                    .map_err(|e| anyhow!("service '{}': {}", self.name, e))?;
+            }
+            if let Some(expected) = &self.response_code {
+                expected
+                    .validate()
+                    .map_err(|e| anyhow!("service '{}': {}", self.name, e))?;
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct StorageSettings {
+        /// Path of the SQLite database holding check history; created on first start.
+        pub path: String,
+    }
+
+    impl Default for StorageSettings {
+        fn default() -> Self {
+            Self {
+                path: "health-check.db".to_string(),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct WebhookSettings {
+        pub name: String,
+        pub url: String,
+        /// Services this webhook is notified about; all services when empty.
+        #[serde(default)]
+        pub services: Vec<String>,
+    }
+
+    impl WebhookSettings {
+        pub fn applies_to(&self, service: &str) -> bool {
+            self.services.is_empty() || self.services.iter().any(|name| name == service)
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct AlertingSettings {
+        pub webhooks: Vec<WebhookSettings>,
+        pub retries: u32,
+        pub retry_delay_ms: u64,
+        pub timeout_seconds: u64,
+        pub notify_recovery: bool,
+    }
+
+    impl Default for AlertingSettings {
+        fn default() -> Self {
+            Self {
+                webhooks: Vec::new(),
+                retries: 3,
+                retry_delay_ms: 1000,
+                timeout_seconds: 5,
+                notify_recovery: true,
+            }
+        }
+    }
+
+    impl AlertingSettings {
+        pub fn validate(&self, services: &[ServiceSettings]) -> Result<()> {
+            for webhook in &self.webhooks {
+                reqwest::Url::parse(&webhook.url).map_err(|e| {
+                    anyhow!("webhook '{}': invalid url '{}': {}", webhook.name, webhook.url, e)
+                })?;
+                for name in &webhook.services {
+                    if !services.iter().any(|service| &service.name == name) {
+                        bail!("webhook '{}': unknown service '{}'", webhook.name, name);
+                    }
+                }
+            }
+            Ok(())
+        }
     }
 
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct ServiceConfiguration {
         #[serde(rename = "globalSettings")]
         pub global: GlobalSettings,
+        #[serde(default)]
+        pub storage: StorageSettings,
+        #[serde(default)]
+        pub alerting: AlertingSettings,
         #[serde(rename = "services")]
         pub services: Vec<ServiceSettings>,
     }
@@ -43,7 +841,71 @@ pub mod configs {
         pub fn load_from_file(path: &str) -> Result<Self> {
             let file = std::fs::read_to_string(path)?;
             let service_config: ServiceConfiguration = toml::from_str(&file)?;
+            for service in &service_config.services {
+                service.validate(&service_config.global)?;
+            }
+            service_config.alerting.validate(&service_config.services)?;
             Ok(service_config)
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn service(toml: &str) -> ServiceSettings {
+            toml::from_str(toml).unwrap()
+        }
+
+        #[test]
+        fn parses_status_ranges() {
+            assert_eq!(ExpectedStatus::parse_range("200-299").unwrap(), (200, 299));
+            assert_eq!(ExpectedStatus::parse_range(" 301 - 302 ").unwrap(), (301, 302));
+            assert!(ExpectedStatus::parse_range("299-200").is_err());
+            assert!(ExpectedStatus::parse_range("200").is_err());
+            assert!(ExpectedStatus::parse_range("2xx-3xx").is_err());
+        }
+
+        #[test]
+        fn matches_codes_ranges_and_lists() {
+            let expected = ExpectedStatus::List(vec![
+                ExpectedStatus::Code(204),
+                ExpectedStatus::Range("300-399".to_string()),
+            ]);
+            assert!(expected.matches(204));
+            assert!(expected.matches(301));
+            assert!(!expected.matches(200));
+            assert!(!ExpectedStatus::Range("oops".to_string()).matches(200));
+            assert!(ExpectedStatus::List(vec![]).validate().is_err());
+            assert!(ExpectedStatus::Code(42).validate().is_err());
+        }
+
+        #[test]
+        fn rejects_a_zero_check_interval() {
+            let global = GlobalSettings::default();
+            let zero = service(
+                r#"
+                name = "a"
+                type = "tcp"
+                url = "localhost:80"
+                check_interval_seconds = 0
+                "#,
+            );
+            assert!(zero.validate(&global).is_err());
+
+            let inherited = service(
+                r#"
+                name = "a"
+                type = "tcp"
+                url = "localhost:80"
+                "#,
+            );
+            assert!(inherited.validate(&global).is_ok());
+            let global = GlobalSettings {
+                check_interval_seconds: 0,
+                ..GlobalSettings::default()
+            };
+            assert!(inherited.validate(&global).is_err());
+        }
+    }
 }
@@ -1,9 +1,12 @@
 use std::fmt::Display;
+use std::ops::AddAssign;
+use std::str::FromStr;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use crate::types::ServiceType;
 
-#[derive(Serialize, Deserialize, Clone)]
+/// Ordered from best to worst, so `max` picks the more severe of two statuses.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum CheckStatus {
     Healthy,
     Degraded,
@@ -18,12 +21,98 @@ impl Display for CheckStatus {
         }
     }
 }
+impl FromStr for CheckStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Healthy" => Ok(CheckStatus::Healthy),
+            "Degraded" => Ok(CheckStatus::Degraded),
+            "Unhealthy" => Ok(CheckStatus::Unhealthy),
+            _ => Err(format!("unknown check status '{}'", s)),
+        }
+    }
+}
 #[derive(Serialize, Deserialize, Clone)]
 pub struct HealthCheckStatus {
     pub status: CheckStatus,
     pub status_message: String,
     pub response_time: u128,
     pub timestamp: DateTime<Utc>,
+    /// Number of tries it took to reach this result, including retries.
+    #[serde(default = "default_attempts")]
+    pub attempts: u32,
+    /// Per-phase breakdown, only recorded by HTTP checks with `record_timings` enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+}
+
+impl HealthCheckStatus {
+    /// A result of a single attempt taken now, without a timing breakdown.
+    pub fn new(status: CheckStatus, status_message: impl Into<String>, response_time: u128) -> Self {
+        HealthCheckStatus {
+            status,
+            status_message: status_message.into(),
+            response_time,
+            timestamp: Utc::now(),
+            attempts: 1,
+            timings: None,
+        }
+    }
+
+    pub fn healthy(status_message: impl Into<String>, response_time: u128) -> Self {
+        Self::new(CheckStatus::Healthy, status_message, response_time)
+    }
+
+    pub fn unhealthy(status_message: impl Into<String>, response_time: u128) -> Self {
+        Self::new(CheckStatus::Unhealthy, status_message, response_time)
+    }
+
+    pub fn with_timings(mut self, timings: Option<Timings>) -> Self {
+        self.timings = timings;
+        self
+    }
+}
+
+/// Durations of the phases of an HTTP check, in milliseconds.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timings {
+    pub dns: u128,
+    pub connect: u128,
+    /// `None` for plain HTTP.
+    pub tls: Option<u128>,
+    /// From sending the request to receiving the response headers.
+    pub ttfb: u128,
+    /// Reading the response body.
+    pub transfer: u128,
+}
+
+/// Adds up the phases of consecutive requests, e.g. along a redirect chain.
+impl AddAssign for Timings {
+    fn add_assign(&mut self, other: Timings) {
+        self.dns += other.dns;
+        self.connect += other.connect;
+        self.tls = match (self.tls, other.tls) {
+            (None, None) => None,
+            (tls, other_tls) => Some(tls.unwrap_or_default() + other_tls.unwrap_or_default()),
+        };
+        self.ttfb += other.ttfb;
+        self.transfer += other.transfer;
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DNS {}ms, connect {}ms", self.dns, self.connect)?;
+        if let Some(tls) = self.tls {
+            write!(f, ", TLS {}ms", tls)?;
+        }
+        write!(f, ", first byte {}ms, transfer {}ms", self.ttfb, self.transfer)
+    }
+}
+
+fn default_attempts() -> u32 {
+    1
 }
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ServiceHealthCheckInfo {
@@ -31,5 +120,10 @@ pub struct ServiceHealthCheckInfo {
     pub service_type: ServiceType,
     pub url: String,
     pub interval_seconds: u64,
+    /// Damped status, only changed after enough consecutive results agree.
     pub latest_status: HealthCheckStatus,
+    /// Result of the most recent check as reported by the checker.
+    pub raw_status: HealthCheckStatus,
+    #[serde(default)]
+    pub flapping: bool,
 }
\ No newline at end of file
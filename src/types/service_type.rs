@@ -1,12 +1,22 @@
 use std::fmt::Display;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ServiceType {
     #[serde(rename = "http")]
     Http,
     #[serde(rename = "db")]
     Db,
+    #[serde(rename = "tcp")]
+    Tcp,
+    #[serde(rename = "tls")]
+    Tls,
+    #[serde(rename = "dns")]
+    Dns,
+    #[serde(rename = "redis")]
+    Redis,
+    #[serde(rename = "grpc")]
+    Grpc,
 }
 
 impl Display for ServiceType {
@@ -14,6 +24,11 @@ impl Display for ServiceType {
         match self {
             ServiceType::Http => write!(f, "http"),
             ServiceType::Db => write!(f, "db"),
+            ServiceType::Tcp => write!(f, "tcp"),
+            ServiceType::Tls => write!(f, "tls"),
+            ServiceType::Dns => write!(f, "dns"),
+            ServiceType::Redis => write!(f, "redis"),
+            ServiceType::Grpc => write!(f, "grpc"),
         }
     }
 }
\ No newline at end of file